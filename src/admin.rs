@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use serde_json::{Map, Value};
+use zenoh::{Session, Wait};
+
+use crate::payload::Payload;
+
+/// Timeout for admin space queries of the TUI
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Key expression within the admin space: `*/router` becomes `@/*/router`
+pub fn admin_keyexpr(path: &str) -> String {
+    let path = path.trim_matches('/');
+    if path == "@" || path.starts_with("@/") {
+        path.to_owned()
+    } else if path.is_empty() {
+        "@/**".to_owned()
+    } else {
+        format!("@/{path}")
+    }
+}
+
+/// Top-level key of the error replies, next to the `@` of the admin space
+pub const ERRORS_KEY: &str = "errors";
+
+/// Query the admin space and combine the replies into one JSON tree split by the key segments.
+///
+/// Error replies, for example of a single plugin, are listed as [`ERRORS_KEY`] next to the tree.
+/// This only fails when there are errors and no successful reply.
+pub fn query(session: &Session, keyexpr: &str, timeout: Duration) -> anyhow::Result<Value> {
+    let replies = session
+        .get(keyexpr)
+        .timeout(timeout)
+        .wait()
+        .map_err(|err| anyhow::anyhow!("Failed to query {keyexpr}: {err}"))?;
+
+    let mut root = Map::new();
+    let mut errors = Vec::new();
+    while let Ok(reply) = replies.recv() {
+        match reply.result() {
+            Ok(sample) => {
+                let payload = Payload::unlimited(sample.payload().to_bytes().to_vec());
+                let value = payload
+                    .select(&[])
+                    .unwrap_or_else(|| Value::String(payload.to_string()));
+                insert(&mut root, sample.key_expr().as_str(), value);
+            }
+            Err(err) => {
+                let payload = Payload::unlimited(err.payload().to_bytes().to_vec());
+                errors.push(payload.to_string());
+            }
+        }
+    }
+    with_errors(keyexpr, root, errors)
+}
+
+fn with_errors(
+    keyexpr: &str,
+    mut root: Map<String, Value>,
+    errors: Vec<String>,
+) -> anyhow::Result<Value> {
+    if errors.is_empty() {
+        return Ok(Value::Object(root));
+    }
+    anyhow::ensure!(
+        !root.is_empty(),
+        "Query of {keyexpr} failed: {}",
+        errors.join("; ")
+    );
+    root.insert(
+        ERRORS_KEY.to_owned(),
+        Value::Array(errors.into_iter().map(Value::String).collect()),
+    );
+    Ok(Value::Object(root))
+}
+
+/// Insert the value at the path of the key.
+///
+/// When a key has a value and keys below it, the value is kept as `value` next to the keys below.
+fn insert(root: &mut Map<String, Value>, key: &str, value: Value) {
+    let mut parts = key.split('/').peekable();
+    let mut current = root;
+    while let Some(part) = parts.next() {
+        let entry = current.entry(part).or_insert(Value::Null);
+        if parts.peek().is_none() {
+            match (entry, value) {
+                (Value::Object(existing), Value::Object(new)) => existing.extend(new),
+                (Value::Object(existing), value) => {
+                    existing.insert("value".to_owned(), value);
+                }
+                (entry, value) => *entry = value,
+            }
+            return;
+        }
+        if !entry.is_object() {
+            let mut object = Map::new();
+            if !entry.is_null() {
+                object.insert("value".to_owned(), entry.take());
+            }
+            *entry = Value::Object(object);
+        }
+        current = entry
+            .as_object_mut()
+            .expect("entry should be an object as it was just ensured");
+    }
+}
+
+pub fn show(session: &Session, path: &str, timeout: Duration) -> anyhow::Result<()> {
+    let keyexpr = admin_keyexpr(path);
+    let tree = query(session, &keyexpr, timeout)?;
+    if tree.as_object().is_some_and(Map::is_empty) {
+        eprintln!("No replies for {keyexpr}");
+        return Ok(());
+    }
+    let json = serde_json::to_string_pretty(&tree).expect("Should be able to format JSON");
+    println!("{json}");
+    Ok(())
+}
+
+#[test]
+fn admin_keyexpr_works() {
+    assert_eq!(admin_keyexpr(""), "@/**");
+    assert_eq!(admin_keyexpr("**"), "@/**");
+    assert_eq!(admin_keyexpr("*/router"), "@/*/router");
+    assert_eq!(admin_keyexpr("@/*/router/"), "@/*/router");
+}
+
+#[test]
+fn errors_are_kept_next_to_good_replies() {
+    let mut root = Map::new();
+    insert(&mut root, "@/a1/router", serde_json::json!({"zid": "a1"}));
+    let tree = with_errors("@/**", root, vec!["storage failed".to_owned()]).unwrap();
+    assert_eq!(
+        tree,
+        serde_json::json!({
+            "@": {"a1": {"router": {"zid": "a1"}}},
+            "errors": ["storage failed"],
+        })
+    );
+
+    let err = with_errors("@/**", Map::new(), vec!["a".to_owned(), "b".to_owned()]).unwrap_err();
+    assert_eq!(err.to_string(), "Query of @/** failed: a; b");
+
+    let tree = with_errors("@/**", Map::new(), Vec::new()).unwrap();
+    assert_eq!(tree, serde_json::json!({}));
+}
+
+#[test]
+fn insert_nests_by_key() {
+    let mut root = Map::new();
+    insert(&mut root, "@/a1/router", serde_json::json!({"zid": "a1"}));
+    insert(
+        &mut root,
+        "@/a1/router/linkstate/routers",
+        Value::String("graph".into()),
+    );
+    insert(&mut root, "@/b2/peer", serde_json::json!(42));
+    assert_eq!(
+        Value::Object(root),
+        serde_json::json!({
+            "@": {
+                "a1": {"router": {"zid": "a1", "linkstate": {"routers": "graph"}}},
+                "b2": {"peer": 42},
+            }
+        })
+    );
+}
+
+#[test]
+fn insert_keeps_value_with_keys_below() {
+    let mut root = Map::new();
+    insert(&mut root, "a", serde_json::json!(1));
+    insert(&mut root, "a/b", serde_json::json!(2));
+    insert(&mut root, "c/d", serde_json::json!(3));
+    insert(&mut root, "c", serde_json::json!(4));
+    assert_eq!(
+        Value::Object(root),
+        serde_json::json!({"a": {"value": 1, "b": 2}, "c": {"d": 3, "value": 4}})
+    );
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use zenoh::sample::SampleKind;
+
+use crate::payload::{JsonSelector, Payload};
+
+/// Numbers at the path are considered unchanged when they differ by at most `max_difference`
+#[derive(Debug, Clone)]
+pub struct Tolerance {
+    pub path: Vec<JsonSelector>,
+    pub max_difference: f64,
+}
+
+/// Whether the current sample is a repeat of the previous one of the same key
+pub fn is_repeat(
+    previous: (SampleKind, &Payload),
+    current: (SampleKind, &Payload),
+    tolerance: Option<&Tolerance>,
+) -> bool {
+    if previous.0 != current.0 {
+        return false;
+    }
+    let number = |payload: &Payload| {
+        let tolerance = tolerance?;
+        let value = payload.select(&tolerance.path)?;
+        value
+            .as_f64()
+            .map(|number| (number, tolerance.max_difference))
+    };
+    match (number(previous.1), number(current.1)) {
+        (Some((previous, max_difference)), Some((current, _))) => {
+            (previous - current).abs() <= max_difference
+        }
+        _ => previous.1 == current.1,
+    }
+}
+
+/// Indices of the samples which changed together with the amount of repeats following each of them.
+///
+/// Used by the history table of the TUI, which has no tolerance so only identical payloads are repeats.
+pub fn collapse_repeats<'p>(
+    samples: impl IntoIterator<Item = (SampleKind, &'p Payload)>,
+) -> Vec<(usize, usize)> {
+    let mut rows = Vec::<(usize, usize)>::new();
+    let mut previous = None;
+    for (index, sample) in samples.into_iter().enumerate() {
+        if let (Some(previous), Some((_, repeats))) = (previous, rows.last_mut()) {
+            if is_repeat(previous, sample, None) {
+                *repeats += 1;
+                continue;
+            }
+        }
+        rows.push((index, 0));
+        previous = Some(sample);
+    }
+    rows
+}
+
+/// Remembers the last reported payload per key to suppress repeats of it
+pub struct ChangesOnly {
+    tolerance: Option<Tolerance>,
+    /// Last reported sample and the amount of repeats since
+    last: HashMap<String, (SampleKind, Payload, usize)>,
+}
+
+impl ChangesOnly {
+    pub fn new(tolerance: Option<Tolerance>) -> Self {
+        Self {
+            tolerance,
+            last: HashMap::new(),
+        }
+    }
+
+    /// `None` for a suppressed repeat, otherwise the amount of repeats suppressed since the previous change of the key
+    pub fn observe(&mut self, keyexpr: &str, kind: SampleKind, payload: &Payload) -> Option<usize> {
+        if let Some((last_kind, last_payload, repeats)) = self.last.get_mut(keyexpr) {
+            if is_repeat(
+                (*last_kind, last_payload),
+                (kind, payload),
+                self.tolerance.as_ref(),
+            ) {
+                *repeats += 1;
+                return None;
+            }
+        }
+        let previous = self
+            .last
+            .insert(keyexpr.to_owned(), (kind, payload.clone(), 0));
+        Some(previous.map_or(0, |(_, _, repeats)| repeats))
+    }
+
+    /// Keys with repeats which were not reported yet as no change followed them, sorted by key.
+    /// The counts are reset.
+    pub fn take_pending(&mut self) -> Vec<(String, usize)> {
+        let mut pending = self
+            .last
+            .iter_mut()
+            .filter(|(_, (_, _, repeats))| *repeats > 0)
+            .map(|(keyexpr, (_, _, repeats))| (keyexpr.clone(), std::mem::take(repeats)))
+            .collect::<Vec<_>>();
+        pending.sort_unstable();
+        pending
+    }
+}
+
+#[cfg(test)]
+fn json(value: serde_json::Value) -> Payload {
+    Payload::Json(value)
+}
+
+#[test]
+fn identical_payload_is_a_repeat() {
+    let a = json(serde_json::json!({"temp": 21.5}));
+    let b = json(serde_json::json!({"temp": 21.6}));
+    assert!(is_repeat(
+        (SampleKind::Put, &a),
+        (SampleKind::Put, &a),
+        None
+    ));
+    assert!(!is_repeat(
+        (SampleKind::Put, &a),
+        (SampleKind::Put, &b),
+        None
+    ));
+    assert!(!is_repeat(
+        (SampleKind::Put, &a),
+        (SampleKind::Delete, &a),
+        None
+    ));
+}
+
+#[test]
+fn tolerance_at_path() {
+    let tolerance = Tolerance {
+        path: JsonSelector::parse_path(".temp").unwrap(),
+        max_difference: 0.5,
+    };
+    let a = json(serde_json::json!({"temp": 21.5, "seq": 1}));
+    let b = json(serde_json::json!({"temp": 21.9, "seq": 2}));
+    let c = json(serde_json::json!({"temp": 22.1, "seq": 3}));
+    let text = Payload::String("on".into());
+    let put = SampleKind::Put;
+    assert!(is_repeat((put, &a), (put, &b), Some(&tolerance)));
+    assert!(!is_repeat((put, &a), (put, &c), Some(&tolerance)));
+    assert!(is_repeat((put, &text), (put, &text), Some(&tolerance)));
+}
+
+#[test]
+fn collapse_repeats_works() {
+    let a = Payload::String("a".into());
+    let b = Payload::String("b".into());
+    let put = SampleKind::Put;
+    let rows = collapse_repeats([(put, &a), (put, &a), (put, &a), (put, &b), (put, &a)]);
+    assert_eq!(rows, [(0, 2), (3, 0), (4, 0)]);
+}
+
+#[test]
+fn changes_only_counts_repeats_per_key() {
+    let mut changes = ChangesOnly::new(None);
+    let a = Payload::String("a".into());
+    let b = Payload::String("b".into());
+    let put = SampleKind::Put;
+    assert_eq!(changes.observe("x", put, &a), Some(0));
+    assert_eq!(changes.observe("y", put, &a), Some(0));
+    assert_eq!(changes.observe("x", put, &a), None);
+    assert_eq!(changes.observe("x", put, &a), None);
+    assert_eq!(changes.observe("x", put, &b), Some(2));
+    assert_eq!(changes.observe("y", put, &b), Some(0));
+}
+
+#[test]
+fn pending_repeats_are_taken_once() {
+    let mut changes = ChangesOnly::new(None);
+    let a = Payload::String("a".into());
+    let put = SampleKind::Put;
+    for keyexpr in ["y", "x", "x", "y", "z", "y"] {
+        changes.observe(keyexpr, put, &a);
+    }
+    assert_eq!(
+        changes.take_pending(),
+        [("x".to_owned(), 1), ("y".to_owned(), 2)]
+    );
+    assert!(changes.take_pending().is_empty());
+}
@@ -1,6 +1,36 @@
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use zenoh::handlers::fifo::FifoChannel;
+use zenoh::sample::SampleKind;
 use zenoh::{Session, Wait};
 
-pub fn clean(session: &Session, keyexpr: &str, dry_run: bool) -> anyhow::Result<()> {
+/// How concrete keys below the key expression are discovered before deleting them one by one
+pub struct Discovery {
+    /// Query the stored values, e.g. from a storage
+    pub query: bool,
+    /// Listen for published samples for this duration
+    pub listen: Option<Duration>,
+}
+
+impl Discovery {
+    const fn is_enabled(&self) -> bool {
+        self.query || self.listen.is_some()
+    }
+}
+
+pub fn clean(
+    session: &Session,
+    keyexpr: &str,
+    discovery: &Discovery,
+    dry_run: bool,
+    yes: bool,
+) -> anyhow::Result<()> {
+    if discovery.is_enabled() {
+        return clean_discovered(session, keyexpr, discovery, dry_run, yes);
+    }
+
     let has_wildcards = keyexpr.contains('*');
     if dry_run {
         if has_wildcards {
@@ -20,6 +50,12 @@ pub fn clean(session: &Session, keyexpr: &str, dry_run: bool) -> anyhow::Result<
         return Ok(());
     }
 
+    clean_key(session, keyexpr)?;
+    println!("Cleaned {keyexpr}");
+    Ok(())
+}
+
+fn clean_key(session: &Session, keyexpr: &str) -> anyhow::Result<()> {
     session
         .put(keyexpr, Vec::<u8>::new())
         .wait()
@@ -28,6 +64,101 @@ pub fn clean(session: &Session, keyexpr: &str, dry_run: bool) -> anyhow::Result<
         .delete(keyexpr)
         .wait()
         .map_err(|err| anyhow::anyhow!(err))?;
-    println!("Cleaned {keyexpr}");
     Ok(())
 }
+
+fn clean_discovered(
+    session: &Session,
+    keyexpr: &str,
+    discovery: &Discovery,
+    dry_run: bool,
+    yes: bool,
+) -> anyhow::Result<()> {
+    let keys = discover(session, keyexpr, discovery)?;
+    if keys.is_empty() {
+        println!("No keys found below {keyexpr}");
+        return Ok(());
+    }
+    println!("Found {} keys below {keyexpr}:", keys.len());
+    for key in &keys {
+        println!("  {key}");
+    }
+
+    if dry_run {
+        println!(
+            "Dry run: would put empty payload and delete these {} keys",
+            keys.len()
+        );
+        return Ok(());
+    }
+    if !yes && !confirm(&format!("Delete these {} keys?", keys.len()))? {
+        println!("Aborted. Use --yes to delete without confirmation.");
+        return Ok(());
+    }
+
+    for key in &keys {
+        clean_key(session, key)?;
+        println!("Cleaned {key}");
+    }
+    Ok(())
+}
+
+/// Concrete keys below the key expression which currently have a value
+fn discover(
+    session: &Session,
+    keyexpr: &str,
+    discovery: &Discovery,
+) -> anyhow::Result<BTreeSet<String>> {
+    let mut keys = BTreeSet::new();
+
+    // Subscribe before querying to not miss samples published in between
+    let subscriber = discovery
+        .listen
+        .map(|listen| {
+            session
+                .declare_subscriber(keyexpr)
+                .with(FifoChannel::default())
+                .wait()
+                .map(|subscriber| (subscriber, Instant::now() + listen))
+                .map_err(|err| anyhow::anyhow!("Failed to subscribe to {keyexpr}: {err}"))
+        })
+        .transpose()?;
+
+    if discovery.query {
+        let replies = session
+            .get(keyexpr)
+            .wait()
+            .map_err(|err| anyhow::anyhow!("Failed to query {keyexpr}: {err}"))?;
+        while let Ok(reply) = replies.recv() {
+            if let Ok(sample) = reply.into_result() {
+                if sample.kind() == SampleKind::Put {
+                    keys.insert(sample.key_expr().as_str().to_owned());
+                }
+            }
+        }
+    }
+
+    if let Some((subscriber, deadline)) = subscriber {
+        while let Ok(Some(sample)) = subscriber.recv_deadline(deadline) {
+            let key = sample.key_expr().as_str();
+            match sample.kind() {
+                SampleKind::Put => keys.insert(key.to_owned()),
+                SampleKind::Delete => keys.remove(key),
+            };
+        }
+    }
+
+    Ok(keys)
+}
+
+/// Ask on the terminal, anything but y or yes is a no
+fn confirm(question: &str) -> anyhow::Result<bool> {
+    print!("{question} [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
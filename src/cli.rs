@@ -6,6 +6,8 @@ pub enum Subcommands {
     /// Delete values for a key expression.
     ///
     /// This sends an empty PUT followed by a DELETE for the key expression.
+    ///
+    /// With --query or --listen-for the concrete keys below the key expression are discovered first, listed and deleted one by one after a confirmation.
     #[command(visible_alias = "c", visible_alias = "clean")]
     Clean {
         /// Key expression which gets cleaned.
@@ -17,28 +19,83 @@ pub enum Subcommands {
         /// Dont delete keys, only log what would be sent
         #[arg(long)]
         dry_run: bool,
-    },
 
-    /// Log values from subscribed key expressions to stdout
-    #[command(visible_alias = "l")]
-    Log {
-        /// Key expressions to watch
+        /// Discover keys by querying the stored values, e.g. from a storage
+        #[arg(short, long)]
+        query: bool,
+
+        /// Discover keys by listening for published samples for this duration, e.g. 5s
+        ///
+        /// Not to be confused with the global --listen endpoints of the Zenoh session.
         #[arg(
-            env = "ZENOHUI_KEYEXPR",
+            long = "listen-for",
+            id = "discover_for",
             value_hint = ValueHint::Other,
-            default_value = "**",
+            value_name = "DURATION",
+            value_parser = humantime::parse_duration,
         )]
-        keyexpr: Vec<String>,
+        listen_for: Option<std::time::Duration>,
 
-        /// Output incoming samples as newline-delimited JSON
+        /// Delete the discovered keys without asking for confirmation
         #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Log values from subscribed key expressions to stdout or rotated files
+    #[command(visible_alias = "l")]
+    Log {
+        /// Key expressions to watch [default: profile key expressions or **]
+        #[arg(env = "ZENOHUI_KEYEXPR", value_hint = ValueHint::Other)]
+        keyexpr: Vec<String>,
+
+        /// Output incoming samples as newline-delimited JSON.
+        ///
+        /// Same as `--format json`.
+        #[arg(short, long, conflicts_with = "format")]
         json: bool,
+
+        /// Output format: text, json, csv, tsv, logfmt or a template like '{time} {key} {payload.temp}' [default: text]
+        ///
+        /// Templates can use {time}, {kind}, {key}, {size}, {payload} and paths into the payload like {payload.sensors[0].temp}.
+        /// Use {{ and }} for literal braces.
+        #[arg(short, long, value_hint = ValueHint::Other)]
+        format: Option<String>,
+
+        /// Format of the time
+        #[arg(long, value_enum, default_value_t = TimeFormat::Local)]
+        time_format: TimeFormat,
+
+        /// Only log the value at this path of JSON or MessagePack payloads, e.g. `.sensors[0].temp`.
+        ///
+        /// Samples without a value at the path are skipped.
+        /// The size (like `{size}` of --format) stays the size of the whole received payload.
+        #[arg(short, long, value_hint = ValueHint::Other, value_name = "PATH")]
+        select: Option<String>,
+
+        #[command(flatten)]
+        filter: LogFilterOptions,
+
+        #[command(flatten)]
+        file: LogFileOptions,
+
+        /// Alert rules to evaluate on incoming samples.
+        ///
+        /// Alerts are printed to stderr.
+        /// See the README for the format of the file.
+        #[arg(
+            long,
+            env = "ZENOHUI_RULES",
+            value_hint = ValueHint::FilePath,
+            value_name = "FILE",
+        )]
+        rules: Option<std::path::PathBuf>,
     },
 
-    /// Wait for the first sample on the given key expression(s) and return its payload to stdout.
+    /// Wait for the first samples on the given key expression(s) and return their payloads to stdout.
     ///
-    /// Returns exactly one payload of the first received sample on the given key expression(s).
-    /// The key expression of the received sample is printed to stderr.
+    /// Returns the payload of the first received sample, or of the first --count samples separated by newlines.
+    /// With --query the stored values (e.g. of a storage) are asked first before waiting for published samples.
+    /// The key expression of each received sample is printed to stderr.
     /// This means that you can handle stdout and stderr separately.
     ///
     /// This can be helpful for scripting to get the current temperature reading and pipe it to somewhere else:
@@ -48,14 +105,12 @@ pub enum Subcommands {
     /// The output is the exact payload in its binary form.
     /// This might be valid ASCII / Unicode but could also be something not intended to be displayed on a terminal.
     /// For a human readable format use `--pretty` or `zenohui log`.
+    ///
+    /// Exits with 0 when all samples were received, 2 when the timeout elapsed before and 1 on other errors.
     #[command(visible_alias = "r", visible_alias = "read")]
     ReadOne {
-        /// Key expressions to watch
-        #[arg(
-            env = "ZENOHUI_KEYEXPR",
-            value_hint = ValueHint::Other,
-            default_value = "**",
-        )]
+        /// Key expressions to watch [default: profile key expressions or **]
+        #[arg(env = "ZENOHUI_KEYEXPR", value_hint = ValueHint::Other)]
         keyexpr: Vec<String>,
 
         /// Parse the payload and print it in a human readable pretty form.
@@ -63,6 +118,142 @@ pub enum Subcommands {
         /// This might not be useful for piping the data.
         #[arg(short, long)]
         pretty: bool,
+
+        /// Only print the value at this path of JSON or MessagePack payloads, e.g. `.sensors[0].temp`.
+        ///
+        /// Strings are printed without quotes, other values as JSON.
+        /// Samples without a value at the path are skipped.
+        #[arg(short, long, value_hint = ValueHint::Other, value_name = "PATH")]
+        select: Option<String>,
+
+        /// Give up after this time, e.g. 10s or 1min [default: wait forever]
+        #[arg(
+            short,
+            long,
+            value_hint = ValueHint::Other,
+            value_parser = humantime::parse_duration,
+        )]
+        timeout: Option<std::time::Duration>,
+
+        /// Amount of samples to read.
+        ///
+        /// With more than one sample each raw payload is followed by a newline.
+        #[arg(
+            short,
+            long,
+            value_hint = ValueHint::Other,
+            value_parser = clap::value_parser!(u32).range(1..),
+            default_value_t = 1,
+        )]
+        count: u32,
+
+        /// Also return deletions which have an empty payload.
+        ///
+        /// The key expression on stderr is followed by `Delete` for them.
+        #[arg(long)]
+        include_deletes: bool,
+
+        /// Query the stored values first (e.g. from a storage) and only wait for a published sample when not enough stored values answered
+        #[arg(short, long)]
+        query: bool,
+    },
+
+    /// Subscribe for a while and print statistics per key.
+    ///
+    /// Shows the amount of messages, the message rate, the time between messages, the payload sizes and gaps between messages longer than the given threshold.
+    #[command(visible_alias = "s")]
+    Stats {
+        /// Key expressions to watch [default: profile key expressions or **]
+        #[arg(env = "ZENOHUI_KEYEXPR", value_hint = ValueHint::Other)]
+        keyexpr: Vec<String>,
+
+        /// How long to collect samples before printing the statistics, e.g. 30s or 5min
+        #[arg(
+            short,
+            long,
+            value_hint = ValueHint::Other,
+            value_parser = humantime::parse_duration,
+            default_value = "30s",
+        )]
+        duration: std::time::Duration,
+
+        /// Time between two messages which is considered a gap
+        ///
+        /// [default: profile gap_threshold or 5s]
+        #[arg(
+            long,
+            value_hint = ValueHint::Other,
+            value_name = "DURATION",
+            value_parser = humantime::parse_duration,
+        )]
+        gap_threshold: Option<std::time::Duration>,
+    },
+
+    /// Watch key expressions and alert when keys stop reporting.
+    ///
+    /// Prints an alert line to stdout when a key was not seen for a multiple of its typical period (or the configured timeout) and when it reports again.
+    #[command(visible_alias = "w")]
+    Watchdog {
+        /// Key expressions to watch [default: profile key expressions or **]
+        #[arg(env = "ZENOHUI_KEYEXPR", value_hint = ValueHint::Other)]
+        keyexpr: Vec<String>,
+
+        #[command(flatten)]
+        stale: StaleOptions,
+
+        /// Exit with a non-zero exit code as soon as a key goes stale
+        #[arg(long)]
+        exit_on_stale: bool,
+    },
+
+    /// Query the Zenoh admin space and print the replies as one JSON tree.
+    ///
+    /// Routers expose their sessions, links, linkstate topology and plugins / storages below `@/<zid>/router`.
+    #[command(visible_alias = "a")]
+    Admin {
+        /// Key expression within the admin space, e.g. '*/router' or '@/*/router/linkstate/**'
+        #[arg(value_hint = ValueHint::Other, default_value = "**")]
+        path: String,
+
+        /// How long to wait for replies, e.g. 5s
+        #[arg(
+            long,
+            value_hint = ValueHint::Other,
+            value_parser = humantime::parse_duration,
+            default_value = "5s",
+        )]
+        timeout: std::time::Duration,
+    },
+
+    /// Show information about the Zenoh session.
+    ///
+    /// Prints the own Zenoh ID, the Zenoh IDs of the connected routers and peers and the locators the session is reachable at.
+    #[command(visible_alias = "i")]
+    Info {
+        /// Output the information as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Discover reachable routers and peers via scouting.
+    ///
+    /// Lists the Zenoh ID, the kind (whatami) and the locators of every node answering within the given time.
+    /// Scouting is configured via the Zenoh config (--config / --cfg).
+    #[command(visible_alias = "sc")]
+    Scout {
+        /// How long to scout, e.g. 3s
+        #[arg(
+            short,
+            long,
+            value_hint = ValueHint::Other,
+            value_parser = humantime::parse_duration,
+            default_value = "3s",
+        )]
+        duration: std::time::Duration,
+
+        /// Output the discovered nodes as newline-delimited JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Publish a value quickly
@@ -92,12 +283,22 @@ pub struct Cli {
     #[clap(subcommand)]
     pub subcommands: Option<Subcommands>,
 
-    /// Key expressions to watch
+    /// Profile of the config file to use.
+    ///
+    /// Profiles are read from `$XDG_CONFIG_HOME/zenohui/config.toml` (usually `~/.config/zenohui/config.toml`).
+    /// Command line arguments and environment variables override the values of the profile.
+    /// Without this argument the `default_profile` of the config file is used, if any.
     #[arg(
-        env = "ZENOHUI_KEYEXPR",
+        long,
+        env = "ZENOHUI_PROFILE",
         value_hint = ValueHint::Other,
-        default_value = "**",
+        value_name = "NAME",
+        global = true,
     )]
+    pub profile: Option<String>,
+
+    /// Key expressions to watch [default: profile key expressions or **]
+    #[arg(env = "ZENOHUI_KEYEXPR", value_hint = ValueHint::Other)]
     pub keyexpr: Vec<String>,
 
     /// Truncate the payloads stored to the given size.
@@ -105,19 +306,197 @@ pub struct Cli {
     /// Payloads bigger than that are truncated and not inspected for formats like JSON or MessagePack.
     /// Only their beginning up to the specified amount of bytes can be viewed.
     /// Increasing this value might result in higher memory consumption especially over time.
+    ///
+    /// [default: 8000]
     #[arg(
         long,
         env = "ZENOHUI_PAYLOAD_SIZE_LIMIT",
         value_hint = ValueHint::Other,
-        default_value_t = 8_000,
     )]
-    pub payload_size_limit: usize,
+    pub payload_size_limit: Option<usize>,
+
+    /// Keep at most this many history entries per key.
+    ///
+    /// Older entries are removed from the local history.
+    /// Keeps all entries by default.
+    #[arg(
+        long,
+        env = "ZENOHUI_RETENTION",
+        value_hint = ValueHint::Other,
+        value_name = "ENTRIES",
+    )]
+    pub retention: Option<usize>,
+
+    /// Time between two messages which the statistics popup considers a gap.
+    ///
+    /// [default: profile gap_threshold or 5s]
+    #[arg(
+        long,
+        value_hint = ValueHint::Other,
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+    )]
+    pub gap_threshold: Option<std::time::Duration>,
+
+    #[command(flatten)]
+    pub stale: StaleOptions,
+
+    /// Alert rules to evaluate on incoming samples.
+    ///
+    /// Matching rules can highlight the key, show a banner, ring the terminal bell, append to an alerts log or run a command.
+    /// See the README for the format of the file.
+    #[arg(
+        long,
+        env = "ZENOHUI_RULES",
+        value_hint = ValueHint::FilePath,
+        value_name = "FILE",
+    )]
+    pub rules: Option<std::path::PathBuf>,
 
     // Keep at the end to not mix the next_help_heading with other options
     #[command(flatten, next_help_heading = "Zenoh Connection")]
     pub zenoh_connection: ZenohConnection,
 }
 
+/// Arguments related to the detection of keys which stopped reporting.
+#[derive(Debug, Args)]
+pub struct StaleOptions {
+    /// Consider a key stale when it was not seen for this multiple of its typical period [default: 3]
+    #[arg(
+        long,
+        env = "ZENOHUI_STALE_FACTOR",
+        value_hint = ValueHint::Other,
+        value_name = "FACTOR",
+    )]
+    pub stale_factor: Option<f64>,
+
+    /// Consider a key stale when it was not seen for this duration, e.g. 30s or 5min.
+    ///
+    /// Replaces the detection based on the typical period of the key.
+    #[arg(
+        long,
+        env = "ZENOHUI_STALE_TIMEOUT",
+        value_hint = ValueHint::Other,
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+    )]
+    pub stale_timeout: Option<std::time::Duration>,
+}
+
+/// Arguments to narrow down which samples are logged.
+#[derive(Debug, Args)]
+pub struct LogFilterOptions {
+    /// Only log samples of this kind
+    #[arg(long, value_enum)]
+    pub kind: Option<KindFilter>,
+
+    /// Only log samples whose payload text matches this regular expression
+    #[arg(long, value_hint = ValueHint::Other, value_name = "REGEX")]
+    pub grep: Option<String>,
+
+    /// Only log samples whose payload matches a condition like '.temp > 20'.
+    ///
+    /// A JSON path followed by one of <, <=, >, >=, ==, != or contains and the value to compare with.
+    /// Can be given multiple times and all of them need to match.
+    #[arg(long = "where", value_hint = ValueHint::Other, value_name = "CONDITION")]
+    pub conditions: Vec<String>,
+
+    /// Don't log keys matching this key expression.
+    ///
+    /// Can be given multiple times.
+    #[arg(long, value_hint = ValueHint::Other, value_name = "KEYEXPR")]
+    pub exclude: Vec<String>,
+
+    /// Log at most one sample per key within this duration, e.g. 1s
+    #[arg(
+        long,
+        value_hint = ValueHint::Other,
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+    )]
+    pub sample_every: Option<std::time::Duration>,
+
+    /// Don't log samples whose payload is identical to the previously logged one of the same key.
+    ///
+    /// The amount of suppressed repeats is printed to stderr once the payload changes.
+    #[arg(long)]
+    pub changes_only: bool,
+
+    /// With --changes-only: consider numbers which differ by at most this amount as unchanged.
+    ///
+    /// Only applies to log, hiding repeats in the history table of the TUI compares the payloads exactly.
+    #[arg(long, requires = "changes_only", value_hint = ValueHint::Other, value_name = "NUMBER")]
+    pub tolerance: Option<f64>,
+
+    /// JSON path of the number compared with --tolerance, e.g. '.temp' [default: whole payload]
+    #[arg(long, requires = "tolerance", value_hint = ValueHint::Other, value_name = "PATH")]
+    pub tolerance_path: Option<String>,
+}
+
+/// Arguments to write the log into rotated files instead of stdout
+#[derive(Debug, Args)]
+pub struct LogFileOptions {
+    /// Write the log into this file instead of stdout.
+    ///
+    /// Lines are appended when the file already exists.
+    /// Alerts and other notes are still printed to stderr.
+    #[arg(short, long, value_hint = ValueHint::FilePath, value_name = "FILE")]
+    pub output: Option<std::path::PathBuf>,
+
+    /// Rotate the file once it would grow beyond this size, e.g. 100M
+    ///
+    /// The rotated file is renamed with the current time appended like demo.log.20261019-163957.123
+    #[arg(
+        long,
+        requires = "output",
+        value_hint = ValueHint::Other,
+        value_name = "SIZE",
+        value_parser = parse_size,
+    )]
+    pub rotate_size: Option<u64>,
+
+    /// Rotate the file once it is open for this duration, e.g. 1h
+    #[arg(
+        long,
+        requires = "output",
+        value_hint = ValueHint::Other,
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+    )]
+    pub rotate_every: Option<std::time::Duration>,
+
+    /// Compress rotated files with gzip
+    #[arg(long, requires = "output")]
+    pub gzip: bool,
+
+    /// Write one file per top-level key, e.g. samples of demo/temp into demo.demo.log for --output demo.log
+    #[arg(long, requires = "output")]
+    pub split_by_key: bool,
+}
+
+/// Size in bytes like 1000, 512K, 100M or 1G with binary units
+fn parse_size(arg: &str) -> Result<u64, String> {
+    let arg = arg.trim();
+    let number_end = arg
+        .find(|char: char| !char.is_ascii_digit())
+        .unwrap_or(arg.len());
+    let (number, unit) = arg.split_at(number_end);
+    let number = number
+        .parse::<u64>()
+        .map_err(|_| format!("{arg:?} does not start with a number"))?;
+    let factor: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return Err(format!("{arg:?} has an unknown unit. Use K, M or G")),
+    };
+    number
+        .checked_mul(factor)
+        .filter(|size| *size > 0)
+        .ok_or_else(|| format!("{arg:?} is not a valid size"))
+}
+
 /// Arguments related to the Zenoh connection.
 #[derive(Debug, Args)]
 pub struct ZenohConnection {
@@ -141,30 +520,187 @@ pub struct ZenohConnection {
     )]
     pub listen: Vec<String>,
 
-    /// Zenoh session mode
+    /// Zenoh session mode [default: client or the mode of the Zenoh config file]
+    #[arg(long, env = "ZENOHUI_MODE", value_enum, global = true)]
+    pub mode: Option<SessionMode>,
+
+    /// Zenoh config file (JSON5) for settings like TLS, authentication or scouting.
+    /// --peer, --listen, --mode and --cfg are applied on top.
+    #[arg(
+        long,
+        env = "ZENOHUI_CONFIG",
+        value_hint = ValueHint::FilePath,
+        value_name = "FILE",
+        global = true,
+    )]
+    pub config: Option<std::path::PathBuf>,
+
+    /// Override a value of the Zenoh config, e.g. --cfg 'scouting/multicast/enabled=false'.
+    /// The value is JSON5 and taken as a string when it is not valid JSON5.
     #[arg(
         long,
-        env = "ZENOHUI_MODE",
-        value_enum,
-        default_value_t = SessionMode::Client,
+        value_name = "KEY=VALUE",
+        value_parser = parse_config_override,
         global = true,
     )]
-    pub mode: SessionMode,
+    pub cfg: Vec<(String, String)>,
+
+    /// Connect to the first router found via scouting. Ignored when --peer is given.
+    #[arg(long, env = "ZENOHUI_SCOUT", global = true)]
+    pub scout: bool,
+
+    #[command(flatten)]
+    pub tls: TlsOptions,
+
+    /// User for the username/password authentication.
+    /// Prompts for the password when --password is not given.
+    #[arg(
+        long,
+        env = "ZENOHUI_USER",
+        value_hint = ValueHint::Username,
+        global = true,
+    )]
+    pub user: Option<String>,
+
+    /// Password for the username/password authentication. Prefer the environment variable or the prompt as arguments are visible to other processes.
+    /// Needs --user or a profile user.
+    #[arg(
+        long,
+        env = "ZENOHUI_PASSWORD",
+        hide_env_values = true,
+        value_parser = parse_password,
+        global = true
+    )]
+    pub password: Option<Password>,
+}
+
+/// Password which is never shown, not even in debug output
+#[derive(Clone)]
+pub struct Password(pub String);
+
+impl std::fmt::Debug for Password {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.write_str("Password(***)")
+    }
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn parse_password(password: &str) -> Result<Password, std::convert::Infallible> {
+    Ok(Password(password.to_owned()))
+}
+
+/// Certificates for `tls/` and `quic/` endpoints
+#[derive(Debug, Args)]
+pub struct TlsOptions {
+    /// Root CA certificate (PEM) to verify the routers / peers with
+    #[arg(
+        long,
+        env = "ZENOHUI_TLS_ROOT_CA",
+        value_hint = ValueHint::FilePath,
+        value_name = "FILE",
+        global = true,
+    )]
+    pub tls_root_ca: Option<std::path::PathBuf>,
+
+    /// Client certificate (PEM) for mutual TLS. Requires --tls-key.
+    #[arg(
+        long,
+        env = "ZENOHUI_TLS_CERT",
+        value_hint = ValueHint::FilePath,
+        value_name = "FILE",
+        requires = "tls_key",
+        global = true,
+    )]
+    pub tls_cert: Option<std::path::PathBuf>,
+
+    /// Private key (PEM) of the client certificate for mutual TLS. Requires --tls-cert.
+    #[arg(
+        long,
+        env = "ZENOHUI_TLS_KEY",
+        value_hint = ValueHint::FilePath,
+        value_name = "FILE",
+        requires = "tls_cert",
+        global = true,
+    )]
+    pub tls_key: Option<std::path::PathBuf>,
+
+    /// Verify that the certificate of the router / peer matches its hostname [default: true]
+    #[arg(
+        long,
+        env = "ZENOHUI_TLS_SERVER_NAME_VERIFICATION",
+        value_name = "BOOL",
+        global = true
+    )]
+    pub tls_server_name_verification: Option<bool>,
+}
+
+fn parse_config_override(arg: &str) -> Result<(String, String), String> {
+    let (key, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("{arg:?} is not in the form KEY=VALUE"))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(format!("{arg:?} has an empty key"));
+    }
+    Ok((key.to_owned(), value.trim().to_owned()))
 }
 
 impl ZenohConnection {
-    pub fn describe(&self) -> String {
-        let mut parts = vec![format!("mode={}", self.mode.as_str())];
+    /// `mTLS` with a client certificate, `TLS` with a root CA or a `tls/` / `quic/` endpoint
+    pub fn security(&self) -> Option<&'static str> {
+        let tls_endpoint = self
+            .peer
+            .iter()
+            .chain(&self.listen)
+            .any(|endpoint| endpoint.starts_with("tls/") || endpoint.starts_with("quic/"));
+        if self.tls.tls_cert.is_some() {
+            Some("mTLS")
+        } else if self.tls.tls_root_ca.is_some() || tls_endpoint {
+            Some("TLS")
+        } else {
+            None
+        }
+    }
+
+    pub fn describe(&self, mode: &str) -> String {
+        let mut parts = vec![format!("mode={mode}")];
+        if let Some(config) = &self.config {
+            parts.push(format!("config={}", config.display()));
+        }
         if !self.peer.is_empty() {
             parts.push(format!("peer={}", self.peer.join(",")));
         }
         if !self.listen.is_empty() {
             parts.push(format!("listen={}", self.listen.join(",")));
         }
+        if let Some(security) = self.security() {
+            parts.push(security.to_owned());
+        }
+        if let Some(user) = &self.user {
+            parts.push(format!("user={user}"));
+        }
         parts.join(" ")
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum KindFilter {
+    Put,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TimeFormat {
+    /// Local time of the day like 16:39:57.000
+    Local,
+    /// UTC date and time like 1996-12-19T15:39:57.000Z
+    Utc,
+    /// Local date and time with the offset like 1996-12-19T16:39:57.000+01:00
+    Rfc3339,
+    /// Milliseconds since the Unix epoch
+    EpochMs,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum SessionMode {
     Client,
@@ -187,3 +723,51 @@ fn verify() {
     use clap::CommandFactory;
     Cli::command().debug_assert();
 }
+
+#[test]
+fn config_override_parses() {
+    assert_eq!(
+        parse_config_override("scouting/multicast/enabled = false"),
+        Ok(("scouting/multicast/enabled".to_owned(), "false".to_owned()))
+    );
+    assert!(parse_config_override("mode").is_err());
+    assert!(parse_config_override("=peer").is_err());
+}
+
+#[test]
+fn size_parses() {
+    assert_eq!(parse_size("1000"), Ok(1000));
+    assert_eq!(parse_size("512K"), Ok(512 * 1024));
+    assert_eq!(parse_size("100 MB"), Ok(100 * 1024 * 1024));
+    assert_eq!(parse_size("1g"), Ok(1024 * 1024 * 1024));
+    assert!(parse_size("M").is_err());
+    assert!(parse_size("0").is_err());
+    assert!(parse_size("5T").is_err());
+}
+
+#[test]
+fn clean_listen_for_is_not_the_listen_endpoint() {
+    let cli = Cli::try_parse_from([
+        "zenohui",
+        "--listen",
+        "tcp/0.0.0.0:7447",
+        "clean",
+        "--listen-for",
+        "5s",
+        "foo/**",
+    ])
+    .unwrap();
+    assert_eq!(cli.zenoh_connection.listen, ["tcp/0.0.0.0:7447"]);
+    let Some(Subcommands::Clean { listen_for, .. }) = cli.subcommands else {
+        panic!("should be the clean subcommand");
+    };
+    assert_eq!(listen_for, Some(std::time::Duration::from_secs(5)));
+
+    let cli = Cli::try_parse_from(["zenohui", "clean", "foo/**", "--listen", "tcp/0.0.0.0:7447"])
+        .unwrap();
+    assert_eq!(cli.zenoh_connection.listen, ["tcp/0.0.0.0:7447"]);
+    let Some(Subcommands::Clean { listen_for, .. }) = cli.subcommands else {
+        panic!("should be the clean subcommand");
+    };
+    assert_eq!(listen_for, None);
+}
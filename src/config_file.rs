@@ -0,0 +1,486 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer};
+
+use crate::cli::{Cli, SessionMode, StaleOptions, Subcommands};
+
+pub const DEFAULT_KEYEXPR: &str = "**";
+pub const DEFAULT_PAYLOAD_SIZE_LIMIT: usize = 8_000;
+
+/// Content of `config.toml`
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    /// Profile used when none is given via `--profile`
+    default_profile: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+    /// Keys per action per section of the TUI for all profiles, validated when the TUI starts
+    #[serde(default, deserialize_with = "deserialize_keys")]
+    keys: KeyBindingSections,
+    /// Colors of the TUI for all profiles: a built-in name or a table, validated when the TUI starts
+    theme: Option<toml::Value>,
+}
+
+/// Named set of defaults for the command line arguments
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    #[serde(default)]
+    peer: Vec<String>,
+    #[serde(default)]
+    listen: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_mode")]
+    mode: Option<SessionMode>,
+    /// Zenoh config file (JSON5)
+    config: Option<PathBuf>,
+    tls_root_ca: Option<PathBuf>,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+    tls_server_name_verification: Option<bool>,
+    /// User for the username/password authentication, the password is prompted for
+    user: Option<String>,
+    #[serde(default)]
+    keyexpr: Vec<String>,
+    payload_size_limit: Option<usize>,
+    retention: Option<usize>,
+    rules: Option<PathBuf>,
+    stale_factor: Option<f64>,
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    stale_timeout: Option<Duration>,
+    /// Gap threshold of `stats` and the statistics popup
+    #[serde(default, deserialize_with = "deserialize_duration")]
+    gap_threshold: Option<Duration>,
+    /// Replaces single key bindings of the top-level `keys`
+    #[serde(default, deserialize_with = "deserialize_keys")]
+    keys: KeyBindingSections,
+    /// Replaces the top-level `theme`
+    theme: Option<toml::Value>,
+}
+
+fn deserialize_mode<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<SessionMode>, D::Error> {
+    let Some(mode) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    SessionMode::from_str(&mode, true)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    let Some(duration) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    humantime::parse_duration(&duration)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Keys per action name per section name as written in the config file
+pub type KeyBindingSections = BTreeMap<String, BTreeMap<String, Vec<String>>>;
+
+/// One key or a list of keys per action
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+fn deserialize_keys<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<KeyBindingSections, D::Error> {
+    let sections = BTreeMap::<String, BTreeMap<String, Keys>>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(section, actions)| {
+            let actions = actions
+                .into_iter()
+                .map(|(action, keys)| match keys {
+                    Keys::One(key) => (action, vec![key]),
+                    Keys::Many(keys) => (action, keys),
+                })
+                .collect();
+            (section, actions)
+        })
+        .collect();
+    Ok(sections)
+}
+
+/// `$XDG_CONFIG_HOME/zenohui/config.toml` with the fallback `~/.config/zenohui/config.toml`
+pub fn path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME")
+                .filter(|dir| !dir.is_empty())
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+    Some(config_home.join("zenohui").join("config.toml"))
+}
+
+impl ConfigFile {
+    /// Load the config file. A missing file is the same as an empty one.
+    pub fn load() -> anyhow::Result<Self> {
+        let Some(path) = path() else {
+            return Ok(Self::default());
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content)
+                .map_err(|err| anyhow::anyhow!("Invalid config file {}: {err}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(anyhow::anyhow!(
+                "Failed to read config file {}: {err}",
+                path.display()
+            )),
+        }
+    }
+
+    fn parse(content: &str) -> anyhow::Result<Self> {
+        let config = toml::from_str::<Self>(content)?;
+        for (name, profile) in &config.profiles {
+            anyhow::ensure!(
+                profile.tls_cert.is_some() == profile.tls_key.is_some(),
+                "Profile {name} needs both tls_cert and tls_key for mutual TLS"
+            );
+        }
+        if let Some(name) = &config.default_profile {
+            anyhow::ensure!(
+                config.profiles.contains_key(name),
+                "default_profile {name} does not exist"
+            );
+        }
+        Ok(config)
+    }
+
+    /// The given profile, the default profile or an empty one.
+    ///
+    /// The top-level `keys` and `theme` are used where the profile does not set them.
+    pub fn into_profile(mut self, name: Option<&str>) -> anyhow::Result<Profile> {
+        let mut profile = match name.or(self.default_profile.as_deref()) {
+            None => Profile::default(),
+            Some(name) => {
+                let name = name.to_owned();
+                self.profiles.remove(&name).ok_or_else(|| {
+                    let available = self.profiles.keys().cloned().collect::<Vec<_>>();
+                    anyhow::anyhow!(
+                        "Profile {name} does not exist in the config file. Available profiles: {}",
+                        available.join(", ")
+                    )
+                })?
+            }
+        };
+        for (section, actions) in std::mem::take(&mut profile.keys) {
+            self.keys.entry(section).or_default().extend(actions);
+        }
+        profile.keys = self.keys;
+        profile.theme = profile.theme.or(self.theme);
+        Ok(profile)
+    }
+}
+
+impl Profile {
+    pub fn keys(&self) -> KeyBindingSections {
+        self.keys.clone()
+    }
+
+    pub fn theme(&self) -> Option<toml::Value> {
+        self.theme.clone()
+    }
+
+    /// Fill everything not given on the command line with the profile values or the defaults
+    pub fn apply(self, cli: &mut Cli) {
+        let connection = &mut cli.zenoh_connection;
+        if connection.peer.is_empty() {
+            connection.peer = self.peer;
+        }
+        if connection.listen.is_empty() {
+            connection.listen = self.listen;
+        }
+        if connection.mode.is_none() {
+            connection.mode = self.mode;
+        }
+        if connection.config.is_none() {
+            connection.config = self.config;
+        }
+        let tls = &mut connection.tls;
+        if tls.tls_root_ca.is_none() {
+            tls.tls_root_ca = self.tls_root_ca;
+        }
+        // Certificate and key belong together
+        if tls.tls_cert.is_none() && tls.tls_key.is_none() {
+            tls.tls_cert = self.tls_cert;
+            tls.tls_key = self.tls_key;
+        }
+        if tls.tls_server_name_verification.is_none() {
+            tls.tls_server_name_verification = self.tls_server_name_verification;
+        }
+        if connection.user.is_none() {
+            connection.user = self.user;
+        }
+
+        let keyexpr = if self.keyexpr.is_empty() {
+            vec![DEFAULT_KEYEXPR.to_owned()]
+        } else {
+            self.keyexpr
+        };
+
+        match &mut cli.subcommands {
+            Some(Subcommands::Log {
+                keyexpr: cli_keyexpr,
+                rules,
+                ..
+            }) => {
+                if rules.is_none() {
+                    *rules = self.rules;
+                }
+                fill_keyexpr(cli_keyexpr, keyexpr);
+            }
+            Some(Subcommands::ReadOne {
+                keyexpr: cli_keyexpr,
+                ..
+            }) => fill_keyexpr(cli_keyexpr, keyexpr),
+            Some(Subcommands::Stats {
+                keyexpr: cli_keyexpr,
+                gap_threshold,
+                ..
+            }) => {
+                *gap_threshold = gap_threshold.or(self.gap_threshold);
+                fill_keyexpr(cli_keyexpr, keyexpr);
+            }
+            Some(Subcommands::Watchdog {
+                keyexpr: cli_keyexpr,
+                stale,
+                ..
+            }) => {
+                fill_stale(stale, self.stale_factor, self.stale_timeout);
+                fill_keyexpr(cli_keyexpr, keyexpr);
+            }
+            Some(
+                Subcommands::Admin { .. }
+                | Subcommands::Clean { .. }
+                | Subcommands::Info { .. }
+                | Subcommands::Publish { .. }
+                | Subcommands::Scout { .. },
+            ) => {}
+            None => {
+                fill_keyexpr(&mut cli.keyexpr, keyexpr);
+                fill_stale(&mut cli.stale, self.stale_factor, self.stale_timeout);
+                if cli.rules.is_none() {
+                    cli.rules = self.rules;
+                }
+                if cli.retention.is_none() {
+                    cli.retention = self.retention;
+                }
+                if cli.payload_size_limit.is_none() {
+                    cli.payload_size_limit = self.payload_size_limit;
+                }
+                cli.gap_threshold = cli.gap_threshold.or(self.gap_threshold);
+            }
+        }
+    }
+}
+
+fn fill_keyexpr(cli: &mut Vec<String>, profile: Vec<String>) {
+    if cli.is_empty() {
+        *cli = profile;
+    }
+}
+
+fn fill_stale(cli: &mut StaleOptions, factor: Option<f64>, timeout: Option<Duration>) {
+    cli.stale_factor = cli.stale_factor.or(factor);
+    cli.stale_timeout = cli.stale_timeout.or(timeout);
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = r#"
+default_profile = "lab"
+
+[profiles.lab]
+peer = ["tcp/10.0.0.1:7447"]
+mode = "peer"
+keyexpr = ["lab/**"]
+payload_size_limit = 16000
+stale_timeout = "30s"
+gap_threshold = "1min"
+
+[profiles.home]
+keyexpr = ["home/**"]
+"#;
+
+#[cfg(test)]
+fn parse_cli(args: &[&str]) -> Cli {
+    use clap::Parser;
+    Cli::try_parse_from(std::iter::once("zenohui").chain(args.iter().copied())).unwrap()
+}
+
+#[test]
+fn parse_example() {
+    let config = ConfigFile::parse(EXAMPLE).unwrap();
+    assert_eq!(config.default_profile.as_deref(), Some("lab"));
+    assert_eq!(config.profiles.len(), 2);
+}
+
+#[test]
+fn parse_fails_on_missing_default_profile() {
+    assert!(ConfigFile::parse("default_profile = \"nope\"").is_err());
+}
+
+#[test]
+fn parse_fails_on_unknown_mode() {
+    assert!(ConfigFile::parse("[profiles.a]\nmode = \"satellite\"").is_err());
+}
+
+#[test]
+fn parse_fails_on_tls_cert_without_key() {
+    assert!(ConfigFile::parse("[profiles.a]\ntls_cert = \"client.pem\"").is_err());
+}
+
+#[test]
+fn unknown_profile_fails() {
+    let config = ConfigFile::parse(EXAMPLE).unwrap();
+    assert!(config.into_profile(Some("office")).is_err());
+}
+
+#[test]
+fn default_profile_is_applied() {
+    let mut cli = parse_cli(&[]);
+    let profile = ConfigFile::parse(EXAMPLE)
+        .unwrap()
+        .into_profile(None)
+        .unwrap();
+    profile.apply(&mut cli);
+    assert_eq!(cli.zenoh_connection.peer, ["tcp/10.0.0.1:7447"]);
+    assert!(matches!(cli.zenoh_connection.mode, Some(SessionMode::Peer)));
+    assert_eq!(cli.keyexpr, ["lab/**"]);
+    assert_eq!(cli.payload_size_limit, Some(16_000));
+    assert_eq!(cli.stale.stale_timeout, Some(Duration::from_secs(30)));
+    assert_eq!(cli.gap_threshold, Some(Duration::from_secs(60)));
+}
+
+#[test]
+fn profile_gap_threshold_applies_to_stats() {
+    let mut cli = parse_cli(&["stats"]);
+    let profile = ConfigFile::parse(EXAMPLE)
+        .unwrap()
+        .into_profile(None)
+        .unwrap();
+    profile.apply(&mut cli);
+    let Some(Subcommands::Stats { gap_threshold, .. }) = cli.subcommands else {
+        panic!("should be the stats subcommand");
+    };
+    assert_eq!(gap_threshold, Some(Duration::from_secs(60)));
+}
+
+#[test]
+fn cli_overrides_profile() {
+    let mut cli = parse_cli(&["--mode", "client", "--payload-size-limit", "42", "foo/**"]);
+    let profile = ConfigFile::parse(EXAMPLE)
+        .unwrap()
+        .into_profile(Some("lab"))
+        .unwrap();
+    profile.apply(&mut cli);
+    assert!(matches!(
+        cli.zenoh_connection.mode,
+        Some(SessionMode::Client)
+    ));
+    assert_eq!(cli.keyexpr, ["foo/**"]);
+    assert_eq!(cli.payload_size_limit, Some(42));
+}
+
+#[test]
+fn defaults_without_profile() {
+    let mut cli = parse_cli(&["log"]);
+    Profile::default().apply(&mut cli);
+    let Some(Subcommands::Log { keyexpr, .. }) = cli.subcommands else {
+        panic!("should be the log subcommand");
+    };
+    assert_eq!(keyexpr, ["**"]);
+}
+
+#[test]
+fn parse_keys() {
+    let config = "[keys.all]\nquit = \"Ctrl+q\"\n\n[keys.topics]\nsearch = [\"/\", \"f\"]";
+    let keys = ConfigFile::parse(config)
+        .unwrap()
+        .into_profile(None)
+        .unwrap()
+        .keys();
+    assert_eq!(keys["all"]["quit"], ["Ctrl+q"]);
+    assert_eq!(keys["topics"]["search"], ["/", "f"]);
+}
+
+#[test]
+fn parse_theme() {
+    let profile = ConfigFile::parse("theme = \"high-contrast\"")
+        .unwrap()
+        .into_profile(None)
+        .unwrap();
+    assert_eq!(
+        profile.theme().as_ref().and_then(toml::Value::as_str),
+        Some("high-contrast")
+    );
+}
+
+#[test]
+fn profile_overrides_keys_and_theme() {
+    let config = r#"
+theme = "light"
+
+[keys.all]
+quit = "Ctrl+q"
+page_down = "PgDn"
+
+[profiles.lab]
+theme = "high-contrast"
+
+[profiles.lab.keys.all]
+quit = "x"
+
+[profiles.home]
+"#;
+    let lab = ConfigFile::parse(config)
+        .unwrap()
+        .into_profile(Some("lab"))
+        .unwrap();
+    assert_eq!(
+        lab.theme().as_ref().and_then(toml::Value::as_str),
+        Some("high-contrast")
+    );
+    assert_eq!(lab.keys()["all"]["quit"], ["x"]);
+    assert_eq!(lab.keys()["all"]["page_down"], ["PgDn"]);
+
+    let home = ConfigFile::parse(config)
+        .unwrap()
+        .into_profile(Some("home"))
+        .unwrap();
+    assert_eq!(
+        home.theme().as_ref().and_then(toml::Value::as_str),
+        Some("light")
+    );
+    assert_eq!(home.keys()["all"]["quit"], ["Ctrl+q"]);
+}
+
+#[test]
+fn profile_user_with_password_from_env() {
+    std::env::set_var("ZENOHUI_PASSWORD", "secret");
+    let mut cli = parse_cli(&["publish", "foo", "bar"]);
+    std::env::remove_var("ZENOHUI_PASSWORD");
+    let profile = ConfigFile::parse("[profiles.lab]\nuser = \"alice\"")
+        .unwrap()
+        .into_profile(Some("lab"))
+        .unwrap();
+    profile.apply(&mut cli);
+    assert_eq!(cli.zenoh_connection.user.as_deref(), Some("alice"));
+    assert_eq!(
+        cli.zenoh_connection.password.map(|password| password.0),
+        Some("secret".to_owned())
+    );
+}
@@ -0,0 +1,17 @@
+use zenoh::Session;
+
+use crate::zenoh_client::{SessionInfo, SessionStatus};
+
+pub fn show(session: &Session, session_info: &SessionInfo, json: bool) {
+    let status = SessionStatus::query(session);
+    if json {
+        let json =
+            serde_json::to_string(&status).expect("Should be able to format session info as JSON");
+        println!("{json}");
+    } else {
+        println!("{:9} {}", "Session", session_info.description);
+        for line in status.lines() {
+            println!("{line}");
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::{Arc, RwLock};
+use std::thread;
+
+use ratatui::layout::{Alignment, Rect};
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Scrollbar, ScrollbarOrientation};
+use ratatui::Frame;
+use tui_tree_widget::{Tree, TreeState};
+use zenoh::Session;
+
+use crate::admin;
+use crate::interactive::theme;
+use crate::payload::{tree_items_from_json, JsonSelector};
+
+/// `None` while the query is running
+type ResultArc = Arc<RwLock<Option<Result<serde_json::Value, String>>>>;
+
+pub struct AdminView {
+    pub state: TreeState<JsonSelector>,
+    pub last_area: Rect,
+    result: ResultArc,
+}
+
+impl Default for AdminView {
+    fn default() -> Self {
+        Self {
+            state: TreeState::default(),
+            last_area: Rect::default(),
+            result: Arc::new(RwLock::new(None)),
+        }
+    }
+}
+
+impl AdminView {
+    /// Query the whole admin space in the background
+    pub fn refresh(&mut self, session: Arc<Session>) {
+        let result = Arc::new(RwLock::new(None));
+        self.result = Arc::clone(&result);
+        thread::Builder::new()
+            .name("zenoh admin space".to_owned())
+            .spawn(move || {
+                let keyexpr = admin::admin_keyexpr("**");
+                let tree = admin::query(&session, &keyexpr, admin::DEFAULT_TIMEOUT)
+                    .map_err(|err| err.to_string());
+                *result.write().expect("admin view reader panicked") = Some(tree);
+            })
+            .expect("should be able to spawn a thread");
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        self.last_area = area;
+        let theme = theme::get(); // The view is only shown when focused
+        let block = Block::new()
+            .border_type(BorderType::Rounded)
+            .borders(Borders::TOP)
+            .title_alignment(Alignment::Center)
+            .border_style(theme.focus);
+
+        let result = self.result.read().expect("admin space thread panicked");
+        let tree = match result.as_ref() {
+            None => {
+                let block = block.title("Admin Space (querying…)");
+                frame.render_widget(Paragraph::new("").block(block), area);
+                return;
+            }
+            Some(Err(err)) => {
+                let block = block.title("Admin Space");
+                let paragraph = Paragraph::new(err.as_str()).style(theme.bad).block(block);
+                frame.render_widget(paragraph, area);
+                return;
+            }
+            Some(Ok(tree)) => tree,
+        };
+
+        let items = tree_items_from_json(tree);
+        let errors = tree
+            .get(admin::ERRORS_KEY)
+            .and_then(serde_json::Value::as_array)
+            .map_or(0, Vec::len);
+        let title = if items.is_empty() {
+            "Admin Space (no replies)".to_owned()
+        } else if errors > 0 {
+            format!(
+                "Admin Space ({errors} failed replies, see {})",
+                admin::ERRORS_KEY
+            )
+        } else {
+            "Admin Space".to_owned()
+        };
+        let widget = Tree::new(&items)
+            .unwrap()
+            .experimental_scrollbar(Some(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None)
+                    .track_symbol(None),
+            ))
+            .highlight_style(theme.selection)
+            .block(block.title(title));
+        frame.render_stateful_widget(widget, area, &mut self.state);
+    }
+}
@@ -1,12 +1,16 @@
-use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::layout::Alignment;
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Text};
 use ratatui::widgets::{Block, Clear, Paragraph};
 use ratatui::Frame;
 
-pub fn draw_popup(frame: &mut Frame, keyexpr: &str) {
+use crate::interactive::keybindings::{Action, Context, KeyBindings};
+use crate::interactive::theme;
+use crate::interactive::ui::popup_area;
+
+pub fn draw_popup(frame: &mut Frame, keyexpr: &str, keybindings: &KeyBindings) {
     let block = Block::bordered()
-        .border_style(Style::new().fg(Color::Red))
+        .border_style(theme::get().bad)
         .title_alignment(Alignment::Center)
         .title("Delete keys");
     let text = vec![
@@ -16,10 +20,14 @@ pub fn draw_popup(frame: &mut Frame, keyexpr: &str) {
             Style::new().add_modifier(Modifier::BOLD | Modifier::ITALIC),
         ),
         Line::raw(""),
-        Line::raw("Confirm with Enter, abort with Esc"),
+        Line::raw(hint(keybindings)),
     ];
     let text = Text::from(text);
-    let area = popup_area(frame.size(), text.width());
+    let area = popup_area(
+        frame.size(),
+        text.width().saturating_add(14),
+        text.height().saturating_add(2),
+    );
     let paragraph = Paragraph::new(text)
         .block(block)
         .alignment(Alignment::Center);
@@ -27,16 +35,46 @@ pub fn draw_popup(frame: &mut Frame, keyexpr: &str) {
     frame.render_widget(paragraph, area);
 }
 
-/// helper function to create a centered area using up certain percentage of the available `area`.
-fn popup_area(area: Rect, text_width: usize) -> Rect {
-    let height = area.height.min(6);
-    let max_width = area.width.saturating_sub(4);
-    #[allow(clippy::cast_possible_truncation)]
-    let width = text_width.saturating_add(14).min(max_width as usize) as u16;
-    Rect {
-        x: area.width.saturating_sub(width) / 2,
-        y: area.height.saturating_sub(height) / 2,
-        width,
-        height,
+/// How to confirm, abort and undo with the configured keys
+fn hint(keybindings: &KeyBindings) -> String {
+    let mut hint = [
+        (Context::CleanPopup, Action::Confirm, "confirm with"),
+        (Context::CleanPopup, Action::Close, "abort with"),
+        (
+            Context::TopicOverview,
+            Action::UndoDelete,
+            "undo later with",
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(context, action, text)| {
+        keybindings
+            .first_key(context, action)
+            .map(|key| format!("{text} {key}"))
+    })
+    .collect::<Vec<_>>()
+    .join(", ");
+    if let Some(first) = hint.get_mut(0..1) {
+        first.make_ascii_uppercase();
     }
+    hint
+}
+
+#[test]
+fn hint_uses_configured_keys() {
+    assert_eq!(
+        hint(&KeyBindings::default()),
+        "Confirm with Enter, abort with Esc, undo later with u"
+    );
+
+    let mut sections = std::collections::BTreeMap::new();
+    sections.insert(
+        "delete_popup".to_owned(),
+        std::collections::BTreeMap::from([("confirm".to_owned(), vec!["y".to_owned()])]),
+    );
+    let keybindings = KeyBindings::default().with_overrides(&sections).unwrap();
+    assert_eq!(
+        hint(&keybindings),
+        "Confirm with y, abort with Esc, undo later with u"
+    );
 }
@@ -0,0 +1,23 @@
+use std::io::Write;
+
+use base64::Engine;
+
+/// Copy the text or raw bytes to the clipboard via the OSC 52 terminal escape sequence.
+///
+/// This also works over SSH but not every terminal supports it.
+pub fn copy(data: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(osc52(data.as_ref()).as_bytes())?;
+    stdout.flush()
+}
+
+fn osc52(data: &[u8]) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+    format!("\x1b]52;c;{encoded}\x07")
+}
+
+#[test]
+fn osc52_works() {
+    assert_eq!(osc52(b".a[0]"), "\x1b]52;c;LmFbMF0=\x07");
+    assert_eq!(osc52(&[0, 255]), "\x1b]52;c;AP8=\x07");
+}
@@ -1,17 +1,18 @@
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratatui::Frame;
 
+use crate::interactive::theme;
+
 pub fn draw(frame: &mut Frame, area: Rect, title: &str, error: &str) {
-    const STYLE: Style = Style::new().fg(Color::Black).bg(Color::Red);
+    let style = theme::get().error;
     let block = Block::new()
-        .border_style(STYLE)
+        .border_style(style)
         .borders(Borders::TOP)
         .title_alignment(Alignment::Center)
         .title(title);
     let paragraph = Paragraph::new(error)
-        .style(STYLE)
+        .style(style)
         .wrap(Wrap { trim: false })
         .block(block);
     frame.render_widget(paragraph, area);
@@ -0,0 +1,74 @@
+use std::time::{Duration, Instant};
+
+use ratatui::layout::Alignment;
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::interactive::theme;
+use crate::interactive::ui::{max_scroll, popup_area, STYLE_BOLD};
+use crate::zenoh_client::{ConnectionEventKind, ConnectionMonitor};
+
+#[derive(Default)]
+pub struct ConnectionPopup {
+    pub scroll: u16,
+    /// Known after the draw as it depends on the amount of events and the terminal size
+    pub max_scroll: u16,
+}
+
+impl ConnectionPopup {
+    pub fn draw(&mut self, frame: &mut Frame, monitor: &ConnectionMonitor) {
+        let theme = theme::get();
+        let now = Instant::now();
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Status: ", STYLE_BOLD),
+                status(monitor, now),
+            ]),
+            Line::from(vec![
+                Span::styled("Last disconnect: ", STYLE_BOLD),
+                Span::raw(
+                    monitor
+                        .last_disconnect()
+                        .map_or_else(|| "never".to_owned(), ToString::to_string),
+                ),
+            ]),
+            Line::raw(""),
+        ];
+        // Newest first
+        lines.extend(monitor.events().iter().rev().map(|event| {
+            let style = match event.kind {
+                ConnectionEventKind::Connected { .. } => theme.good,
+                ConnectionEventKind::Disconnected { .. } => theme.bad,
+                ConnectionEventKind::Error(_) => theme.error,
+            };
+            Line::styled(format!("{:12} {event}", event.time.to_string()), style)
+        }));
+        let text = Text::from(lines);
+
+        let block = Block::bordered()
+            .border_style(theme.focus)
+            .title_alignment(Alignment::Center)
+            .title(format!("Connection Events ({})", monitor.events().len()));
+        let width = text.width().max(40).saturating_add(4);
+        let area = popup_area(frame.size(), width, text.height().saturating_add(2));
+        self.max_scroll = max_scroll(text.height(), area);
+        self.scroll = self.scroll.min(self.max_scroll);
+        let paragraph = Paragraph::new(text).block(block).scroll((self.scroll, 0));
+        frame.render_widget(Clear, area); // clear the background of the popup
+        frame.render_widget(paragraph, area);
+    }
+}
+
+/// Short status like `● connected 1h 2m` for the header
+pub fn status(monitor: &ConnectionMonitor, now: Instant) -> Span<'static> {
+    let theme = theme::get();
+    if let Some(uptime) = monitor.uptime(now) {
+        let uptime = humantime::format_duration(Duration::from_secs(uptime.as_secs()));
+        Span::styled(format!("● connected {uptime}"), theme.good)
+    } else if let Some(since) = monitor.last_disconnect() {
+        Span::styled(format!("● disconnected since {since}"), theme.bad)
+    } else {
+        Span::styled("● disconnected", theme.bad)
+    }
+}
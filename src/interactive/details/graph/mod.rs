@@ -1,11 +1,11 @@
 use chrono::NaiveDateTime;
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Style};
 use ratatui::text::Span;
 use ratatui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType};
 use ratatui::{symbols, Frame};
 
 use self::point::Point;
+use crate::interactive::theme;
 use crate::zenoh_client::HistoryEntry;
 use crate::payload::JsonSelector;
 
@@ -59,11 +59,10 @@ impl Graph {
     }
 
     pub fn draw(&self, frame: &mut Frame, area: Rect) {
-        const STYLE: Style = Style::new().fg(Color::LightGreen);
         let dataset = Dataset::default()
             .graph_type(GraphType::Line)
             .marker(symbols::Marker::Braille)
-            .style(STYLE)
+            .style(theme::get().graph)
             .data(&self.data);
         let chart = Chart::new(vec![dataset])
             .block(
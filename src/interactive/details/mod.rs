@@ -2,6 +2,7 @@ use ratatui::layout::Rect;
 use ratatui::Frame;
 use ratatui_logline_table::State as TableState;
 
+use crate::changes;
 use crate::interactive::ui::{split_area_vertically, ElementInFocus};
 use crate::zenoh_client::HistoryEntry;
 
@@ -14,14 +15,50 @@ pub struct Details {
     pub table_state: TableState,
     pub last_table_area: Rect,
     pub payload: payload_view::PayloadView,
+    /// Hide history entries identical to the previous one
+    pub changes_only: bool,
+    /// Index into the history of each table row together with the amount of hidden repeats after it.
+    /// Updated once per draw and event by [`Self::update_rows`] instead of on every lookup.
+    rows: Vec<(usize, usize)>,
 }
 
 impl Details {
-    pub fn selected_history_index(&self, topic_history_length: usize) -> usize {
-        self.table_state
+    /// Calculate the rows of the history table of the selected topic
+    pub fn update_rows(&mut self, topic_history: &[HistoryEntry]) {
+        self.rows = if self.changes_only {
+            changes::collapse_repeats(
+                topic_history
+                    .iter()
+                    .map(|entry| (entry.kind, &entry.payload)),
+            )
+        } else {
+            (0..topic_history.len()).map(|index| (index, 0)).collect()
+        };
+    }
+
+    pub fn selected_history_index(&self, topic_history: &[HistoryEntry]) -> usize {
+        let last = topic_history.len().saturating_sub(1);
+        let selected = self.table_state.selected().unwrap_or(usize::MAX);
+        if !self.changes_only {
+            return selected.min(last);
+        }
+        let row = selected.min(self.rows.len().saturating_sub(1));
+        // The rows might be from before the history changed
+        self.rows.get(row).map_or(0, |(index, _)| *index).min(last)
+    }
+
+    /// Keeps the selection on the same history entry or the change it is a repeat of
+    pub fn toggle_changes_only(&mut self, topic_history: &[HistoryEntry]) {
+        let selected = self
+            .table_state
             .selected()
-            .unwrap_or(usize::MAX)
-            .min(topic_history_length.saturating_sub(1))
+            .map(|_| self.selected_history_index(topic_history));
+        self.changes_only = !self.changes_only;
+        self.update_rows(topic_history);
+        if let Some(selected) = selected {
+            let row = self.rows.iter().rposition(|(index, _)| *index <= selected);
+            self.table_state.select(row);
+        }
     }
 
     pub fn draw(
@@ -32,12 +69,12 @@ impl Details {
         focus: &ElementInFocus,
     ) {
         let entry = topic_history
-            .get(self.selected_history_index(topic_history.len()))
+            .get(self.selected_history_index(topic_history))
             .expect("when Details are drawn they should always have at least one HistoryEntry");
         let history_area = self.payload.draw(
             frame,
             full_area,
-            matches!(focus, ElementInFocus::Payload),
+            matches!(focus, ElementInFocus::Payload | ElementInFocus::PayloadPath),
             entry,
         );
         let binary_address = self.payload.binary_state.selected_address();
@@ -58,6 +95,7 @@ impl Details {
             frame,
             table_area,
             topic_history,
+            &self.rows,
             binary_address,
             json_selector,
             &mut self.table_state,
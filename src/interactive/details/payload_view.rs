@@ -1,14 +1,14 @@
 use std::cmp::min;
 
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Style};
 use ratatui::text::Text;
 use ratatui::widgets::{Block, BorderType, Paragraph, Scrollbar, ScrollbarOrientation};
 use ratatui::Frame;
 use ratatui_binary_data_widget::{BinaryDataWidget, BinaryDataWidgetState};
 use tui_tree_widget::{Tree, TreeState};
 
-use crate::interactive::ui::{focus_color, split_area_vertically, BORDERS_TOP_RIGHT};
+use crate::interactive::theme;
+use crate::interactive::ui::{split_area_vertically, BORDERS_TOP_RIGHT};
 use crate::zenoh_client::HistoryEntry;
 use crate::payload::{tree_items_from_json, tree_items_from_messagepack, JsonSelector, Payload};
 
@@ -17,6 +17,8 @@ pub struct PayloadView {
     pub binary_state: BinaryDataWidgetState,
     pub json_state: TreeState<JsonSelector>,
     pub last_area: Rect,
+    /// Typed path to jump to in the JSON / MessagePack tree
+    pub path_input: String,
 }
 
 impl PayloadView {
@@ -54,6 +56,16 @@ impl PayloadView {
         (payload_area, remaining_area)
     }
 
+    /// Include the path of the selection
+    fn tree_title(&self, kind: &str, payload_bytes: usize) -> String {
+        let path = JsonSelector::format_path(self.json_state.selected());
+        if path == "." {
+            format!("{kind} Payload (Bytes: {payload_bytes})")
+        } else {
+            format!("{kind} Payload {path} (Bytes: {payload_bytes})")
+        }
+    }
+
     fn draw_binary(
         &mut self,
         frame: &mut Frame,
@@ -64,15 +76,15 @@ impl PayloadView {
     ) -> Rect {
         let title = format!("Binary Payload (Bytes: {payload_bytes})");
 
-        let focus_color = focus_color(has_focus);
+        let theme = theme::get();
         let widget = BinaryDataWidget::new(data)
-            .highlight_style(Style::new().fg(Color::Black).bg(focus_color))
+            .highlight_style(theme.highlight(has_focus))
             .block(
                 Block::new()
                     .border_type(BorderType::Rounded)
                     .borders(BORDERS_TOP_RIGHT)
                     .title_alignment(Alignment::Center)
-                    .border_style(Style::new().fg(focus_color))
+                    .border_style(theme.border(has_focus))
                     .title(title),
             );
 
@@ -91,7 +103,7 @@ impl PayloadView {
         payload_bytes: usize,
         json: &serde_json::Value,
     ) -> Rect {
-        let title = format!("JSON Payload (Bytes: {payload_bytes})");
+        let title = self.tree_title("JSON", payload_bytes);
         let items = tree_items_from_json(json);
 
         let visible = self.json_state.flatten(&items);
@@ -101,7 +113,7 @@ impl PayloadView {
             .sum::<usize>();
         let (payload_area, remaining_area) = self.areas(area, has_focus, content_height);
 
-        let focus_color = focus_color(has_focus);
+        let theme = theme::get();
         let widget = Tree::new(&items)
             .unwrap()
             .experimental_scrollbar(Some(
@@ -110,13 +122,13 @@ impl PayloadView {
                     .end_symbol(None)
                     .track_symbol(None),
             ))
-            .highlight_style(Style::new().fg(Color::Black).bg(focus_color))
+            .highlight_style(theme.highlight(has_focus))
             .block(
                 Block::new()
                     .border_type(BorderType::Rounded)
                     .borders(BORDERS_TOP_RIGHT)
                     .title_alignment(Alignment::Center)
-                    .border_style(Style::new().fg(focus_color))
+                    .border_style(theme.border(has_focus))
                     .title(title),
             );
         frame.render_stateful_widget(widget, payload_area, &mut self.json_state);
@@ -131,7 +143,7 @@ impl PayloadView {
         payload_bytes: usize,
         messagepack: &rmpv::Value,
     ) -> Rect {
-        let title = format!("MessagePack Payload (Bytes: {payload_bytes})");
+        let title = self.tree_title("MessagePack", payload_bytes);
         let items = tree_items_from_messagepack(messagepack);
 
         let visible = self.json_state.flatten(&items);
@@ -141,7 +153,7 @@ impl PayloadView {
             .sum::<usize>();
         let (payload_area, remaining_area) = self.areas(area, has_focus, content_height);
 
-        let focus_color = focus_color(has_focus);
+        let theme = theme::get();
         let widget = Tree::new(&items)
             .unwrap()
             .experimental_scrollbar(Some(
@@ -150,13 +162,13 @@ impl PayloadView {
                     .end_symbol(None)
                     .track_symbol(None),
             ))
-            .highlight_style(Style::new().fg(Color::Black).bg(focus_color))
+            .highlight_style(theme.highlight(has_focus))
             .block(
                 Block::new()
                     .border_type(BorderType::Rounded)
                     .borders(BORDERS_TOP_RIGHT)
                     .title_alignment(Alignment::Center)
-                    .border_style(Style::new().fg(focus_color))
+                    .border_style(theme.border(has_focus))
                     .title(title),
             );
         frame.render_stateful_widget(widget, payload_area, &mut self.json_state);
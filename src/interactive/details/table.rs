@@ -1,28 +1,38 @@
 use std::fmt::Write;
 
 use ratatui::layout::{Alignment, Constraint, Rect};
-use ratatui::style::{Color, Style};
 use ratatui::text::Line;
 use ratatui::widgets::{Block, BorderType};
 use ratatui::Frame;
 use ratatui_logline_table::{State as TableState, Table};
 
 use crate::format;
-use crate::interactive::ui::{focus_color, BORDERS_TOP_RIGHT, STYLE_BOLD};
+use crate::interactive::theme;
+use crate::interactive::ui::{BORDERS_TOP_RIGHT, STYLE_BOLD};
 use crate::zenoh_client::HistoryEntry;
 use crate::payload::{JsonSelector, Payload};
 
-#[allow(clippy::cast_precision_loss, clippy::too_many_lines)]
+/// `rows` are the indices into `topic_history` to show together with the amount of hidden repeats after them
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::too_many_arguments,
+    clippy::too_many_lines
+)]
 pub fn draw(
     frame: &mut Frame,
     area: Rect,
     topic_history: &[HistoryEntry],
+    rows: &[(usize, usize)],
     binary_address: Option<usize>,
     json_selector: &[JsonSelector],
     state: &mut TableState,
     has_focus: bool,
 ) {
     let mut title = format!("History ({}", topic_history.len());
+    let hidden = topic_history.len().saturating_sub(rows.len());
+    if hidden > 0 {
+        write!(title, ", {hidden} repeats hidden").expect("write to string should never fail");
+    }
 
     {
         let with_time = topic_history
@@ -49,18 +59,22 @@ pub fn draw(
     }
     title += ")";
 
-    let last_index = topic_history.len().saturating_sub(1);
-    let focus_color = focus_color(has_focus);
+    let last_index = rows.len().saturating_sub(1);
+    let theme = theme::get();
     let json_selector = json_selector.to_vec();
+    let rows = rows
+        .iter()
+        .map(|(index, repeats)| (&topic_history[*index], *repeats))
+        .collect::<Vec<_>>();
 
     let table = Table::new(
-        topic_history,
+        &rows,
         [
             Constraint::Length(12),
             Constraint::Length(8),
             Constraint::Percentage(100),
         ],
-        move |index, entry| {
+        move |index, &(entry, repeats)| {
             let time = entry.time.to_string();
             let kind = format::kind(entry.kind).to_owned();
             let value = match &entry.payload {
@@ -77,6 +91,11 @@ pub fn draw(
                 }
                 Payload::String(str) => str.to_string(),
             };
+            let value = if repeats > 0 {
+                format!("{value} (repeated {repeats}×)")
+            } else {
+                value
+            };
 
             if index == last_index {
                 [
@@ -91,13 +110,13 @@ pub fn draw(
     )
     .header([Line::raw("Time"), Line::raw("Kind"), Line::raw("Value")])
     .header_style(STYLE_BOLD)
-    .row_highlight_style(Style::new().fg(Color::Black).bg(focus_color))
+    .row_highlight_style(theme.highlight(has_focus))
     .block(
         Block::new()
             .border_type(BorderType::Rounded)
             .borders(BORDERS_TOP_RIGHT)
             .title_alignment(Alignment::Center)
-            .border_style(Style::new().fg(focus_color))
+            .border_style(theme.border(has_focus))
             .title(title),
     );
     frame.render_stateful_widget(table, area, state);
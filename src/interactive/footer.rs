@@ -1,121 +1,229 @@
+use std::time::{Duration, Instant};
+
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
-use crate::interactive::{App, ElementInFocus};
+use crate::interactive::keybindings::{Action, Context};
+use crate::interactive::{theme, App, ElementInFocus};
+use crate::payload::Payload;
 use crate::zenoh_client::SessionInfo;
 
 const VERSION_TEXT: &str = concat!(" zenohui ", env!("CARGO_PKG_VERSION"), " ");
-const VERSION_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Gray);
-const KEY_STYLE: Style = Style::new()
-    .fg(Color::Black)
-    .bg(Color::Gray)
-    .add_modifier(Modifier::BOLD);
+
+/// How long a message like "Copied key" is shown
+const MESSAGE_DURATION: Duration = Duration::from_secs(3);
 
 pub struct Footer {
-    session: Box<str>,
-    full_info: Box<str>,
+    description: Box<str>,
+    session_style: Style,
+    /// Short feedback of an action, shown after the keys until it expires
+    message: Option<Message>,
+}
+
+struct Message {
+    text: String,
+    is_error: bool,
+    since: Instant,
 }
 
 impl Footer {
     pub fn new(session_info: &SessionInfo) -> Self {
+        let theme = theme::get();
         Self {
-            session: format!(" {} ", session_info.description).into(),
-            full_info: format!("{VERSION_TEXT}@ {} ", session_info.description).into(),
+            description: session_info.description.clone().into(),
+            session_style: if session_info.security.is_some() {
+                theme.label
+            } else {
+                theme.key
+            }
+            .remove_modifier(Modifier::BOLD),
+            message: None,
         }
     }
 
+    pub fn show_message(&mut self, text: String, is_error: bool) {
+        self.message = Some(Message {
+            text,
+            is_error,
+            since: Instant::now(),
+        });
+    }
+
     pub fn draw(&self, frame: &mut Frame, area: Rect, app: &App) {
+        let theme = theme::get();
         let mut keys = Vec::new();
 
+        let context = Context::of(&app.focus);
+        let key_hint = |actions: &[Action]| {
+            let keys = actions
+                .iter()
+                .filter_map(|action| app.keybindings.first_key(context, *action))
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            (!keys.is_empty()).then(|| format!(" {} ", keys.join("/")))
+        };
+
         macro_rules! add {
+            ($($action:ident)/+, $text:literal) => {
+                if let Some(hint) = key_hint(&[$(Action::$action),+]) {
+                    keys.push(Span::styled(hint, theme.key));
+                    keys.push(Span::raw(concat![" ", $text, " "]));
+                }
+            };
             ($key:literal, $text:literal) => {
-                keys.push(Span {
-                    content: std::borrow::Cow::Borrowed(concat![" ", $key, " "]),
-                    style: KEY_STYLE,
-                });
-                keys.push(Span {
-                    content: std::borrow::Cow::Borrowed(concat![" ", $text, " "]),
-                    style: Style::new(),
-                });
+                keys.push(Span::styled(concat![" ", $key, " "], theme.key));
+                keys.push(Span::raw(concat![" ", $text, " "]));
             };
         }
 
         match app.focus {
             ElementInFocus::TopicOverview => {
-                add!("q", "Quit");
-                add!("/", "Search");
-                add!("o", "Open all");
+                add!(Quit, "Quit");
+                add!(Help, "Help");
+                add!(Search, "Search");
+                add!(OpenAll, "Open all");
                 if !app.topic_overview.state.opened().is_empty() {
-                    add!("O", "Close all");
+                    add!(CloseAll, "Close all");
+                }
+                if app.topic_overview.stale_only {
+                    add!(ToggleStaleOnly, "Show all");
+                } else {
+                    add!(ToggleStaleOnly, "Only stale");
+                }
+                if app.topic_overview.get_selected().is_some() {
+                    add!(Statistics, "Statistics");
+                    add!(DeleteKeys, "Delete keys");
+                }
+                if !app.undo.is_empty() {
+                    add!(UndoDelete, "Undo delete");
                 }
                 if app.topic_overview.get_selected().is_some() {
-                    add!("Del", "Delete keys");
+                    add!(CopyKey / CopyLatestPayload, "Copy key/payload");
                 }
+                add!(SessionInfo, "Session");
+                add!(AdminSpace, "Admin space");
+                add!(Connection, "Connection");
                 if app.can_switch_to_payload() {
-                    add!("Tab", "Switch to Payload");
+                    add!(NextFocus, "Switch to Payload");
                 } else if app.can_switch_to_history_table() {
-                    add!("Tab", "Switch to History");
+                    add!(NextFocus, "Switch to History");
                 } else {
                     // Changing somewhere is pointless currently
                 }
             }
             ElementInFocus::TopicSearch => {
-                add!("↑", "Before");
-                add!("↓", "Next");
-                add!("Enter", "Open All");
-                add!("Esc", "Clear");
-                keys.push(Span::styled(
-                    " Search: ",
-                    Style::new()
-                        .fg(Color::Black)
-                        .bg(Color::LightGreen)
-                        .add_modifier(Modifier::BOLD),
-                ));
+                add!(PreviousMatch, "Before");
+                add!(NextMatch, "Next");
+                add!(OpenMatches, "Open All");
+                add!(Close, "Clear");
+                keys.push(Span::styled(" Search: ", theme.label));
                 keys.push(Span::raw(" "));
                 keys.push(Span::raw(&app.topic_overview.search));
             }
             ElementInFocus::Payload => {
-                add!("q", "Quit");
+                add!(Quit, "Quit");
+                add!(Help, "Help");
+                if matches!(
+                    app.get_selected_payload(),
+                    Some(Payload::Json(_) | Payload::MessagePack(_))
+                ) {
+                    add!(JumpToPath, "Jump to path");
+                    add!(CopyPath / CopyValue, "Copy path/value");
+                    add!(CopyPretty, "Copy pretty");
+                }
                 #[allow(clippy::branches_sharing_code)]
                 if app.can_switch_to_history_table() {
-                    add!("Tab", "Switch to History");
+                    add!(NextFocus, "Switch to History");
                 } else {
-                    add!("Tab", "Switch to Topics");
+                    add!(NextFocus, "Switch to Topics");
                 }
             }
+            ElementInFocus::PayloadPath => {
+                add!(Confirm, "Jump");
+                add!(Close, "Cancel");
+                let style = if app.get_typed_payload_path().is_some() {
+                    theme.label
+                } else {
+                    theme.error.add_modifier(Modifier::BOLD)
+                };
+                keys.push(Span::styled(" Path: ", style));
+                keys.push(Span::raw(" "));
+                keys.push(Span::raw(&app.details.payload.path_input));
+            }
             ElementInFocus::HistoryTable => {
-                add!("q", "Quit");
-                add!("Tab", "Switch to Topics");
+                add!(Quit, "Quit");
+                add!(Help, "Help");
+                add!(NextFocus, "Switch to Topics");
+                add!(CopyRow, "Copy row");
+                if app.details.changes_only {
+                    add!(ToggleRepeats, "Show repeats");
+                } else {
+                    add!(ToggleRepeats, "Hide repeats");
+                }
+            }
+            ElementInFocus::AdminView => {
+                add!(Quit, "Quit");
+                add!(Help, "Help");
+                add!(Close, "Back");
+                add!(Refresh, "Refresh");
+            }
+            ElementInFocus::ConnectionPopup => {
+                add!(Up / Down, "Scroll");
+                add!(Close, "Close");
             }
             ElementInFocus::CleanPopup(_) => {
-                add!("Enter", "Delete key tree");
+                add!(Confirm, "Delete key tree");
                 add!("Any", "Abort");
             }
+            ElementInFocus::StatisticsPopup(_) | ElementInFocus::SessionPopup => {
+                add!(Close, "Close");
+            }
+        }
+        if let Some(message) = self
+            .message
+            .as_ref()
+            .filter(|message| message.since.elapsed() < MESSAGE_DURATION)
+        {
+            let style = if message.is_error {
+                theme.error
+            } else {
+                theme.label
+            };
+            keys.push(Span::styled(format!(" {} ", message.text), style));
         }
         let keys = Line::from(keys);
 
         #[allow(clippy::cast_possible_truncation)]
-        if matches!(app.focus, ElementInFocus::TopicSearch) {
+        if matches!(
+            app.focus,
+            ElementInFocus::TopicSearch | ElementInFocus::PayloadPath
+        ) {
             let x = area.left().saturating_add(keys.width() as u16);
             frame.set_cursor(x, area.y);
         }
 
         // Show version / session info when enough space
         {
+            let summary = app.zenoh_thread.get_session_status().summary();
+            let full_info = format!("{VERSION_TEXT}@ {} ({summary}) ", self.description);
+            let session = format!(" {} ({summary}) ", self.description);
+            let summary = format!(" {summary} ");
             let remaining = (area.width as usize).saturating_sub(keys.width());
-            let text = if remaining > self.full_info.len() {
-                Some(&*self.full_info)
-            } else if remaining > self.session.len() {
-                Some(&*self.session)
+            let text = if remaining > full_info.len() {
+                Some((full_info.as_str(), self.session_style))
+            } else if remaining > session.len() {
+                Some((session.as_str(), self.session_style))
+            } else if remaining > summary.len() {
+                Some((summary.as_str(), self.session_style))
             } else if remaining > VERSION_TEXT.len() {
-                Some(VERSION_TEXT)
+                Some((VERSION_TEXT, theme.key.remove_modifier(Modifier::BOLD)))
             } else {
                 None // Not enough space -> show nothing
             };
-            if let Some(text) = text {
+            if let Some((text, style)) = text {
                 #[allow(clippy::cast_possible_truncation)]
                 let area = Rect {
                     x: area.width.saturating_sub(text.len() as u16),
@@ -123,7 +231,7 @@ impl Footer {
                     width: text.len() as u16,
                     height: 1,
                 };
-                frame.render_widget(Paragraph::new(text).style(VERSION_STYLE), area);
+                frame.render_widget(Paragraph::new(text).style(style), area);
             }
         }
     }
@@ -0,0 +1,88 @@
+use ratatui::layout::Alignment;
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::interactive::keybindings::{Context, KeyBindings, QUIT_ALWAYS};
+use crate::interactive::theme;
+use crate::interactive::ui::{max_scroll, popup_area, STYLE_BOLD};
+
+/// Overlay listing every key binding of the context it was opened from
+pub struct HelpPopup {
+    context: Context,
+    pub scroll: u16,
+    /// Known after the draw as it depends on the amount of bindings and the terminal size
+    pub max_scroll: u16,
+}
+
+impl HelpPopup {
+    pub const fn new(context: Context) -> Self {
+        Self {
+            context,
+            scroll: 0,
+            max_scroll: 0,
+        }
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, keybindings: &KeyBindings) {
+        let theme = theme::get();
+        let list = keybindings
+            .list(self.context)
+            .into_iter()
+            .map(|(action, keys)| {
+                let keys = keys
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (keys, action)
+            })
+            .collect::<Vec<_>>();
+        let quit_always = QUIT_ALWAYS.to_string();
+        let key_width = list
+            .iter()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max(quit_always.len());
+
+        let mut lines = list
+            .into_iter()
+            .map(|(keys, action)| {
+                Line::from(vec![
+                    Span::styled(format!("{keys:key_width$}  "), STYLE_BOLD),
+                    Span::raw(action.description()),
+                    Span::styled(format!("  {}", action.name()), theme.dim),
+                ])
+            })
+            .collect::<Vec<_>>();
+        lines.push(Line::from(vec![
+            Span::styled(format!("{quit_always:key_width$}  "), STYLE_BOLD),
+            Span::raw("Quit"),
+        ]));
+        match self.context {
+            Context::TopicSearch | Context::PayloadPath => {
+                lines.push(Line::raw(""));
+                lines.push(Line::raw("Other characters are typed"));
+            }
+            Context::CleanPopup => {
+                lines.push(Line::raw(""));
+                lines.push(Line::raw("Any other key aborts too"));
+            }
+            _ => {}
+        }
+        let text = Text::from(lines);
+
+        let block = Block::bordered()
+            .border_style(theme.focus)
+            .title_alignment(Alignment::Center)
+            .title(format!("Key Bindings: {}", self.context.title()));
+        let width = text.width().max(40).saturating_add(4);
+        let area = popup_area(frame.size(), width, text.height().saturating_add(2));
+        self.max_scroll = max_scroll(text.height(), area);
+        self.scroll = self.scroll.min(self.max_scroll);
+        let paragraph = Paragraph::new(text).block(block).scroll((self.scroll, 0));
+        frame.render_widget(Clear, area); // clear the background of the popup
+        frame.render_widget(paragraph, area);
+    }
+}
@@ -0,0 +1,869 @@
+use std::collections::BTreeMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::interactive::ui::ElementInFocus;
+
+/// Group of key bindings which are active together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    TopicOverview,
+    TopicSearch,
+    Payload,
+    PayloadPath,
+    HistoryTable,
+    AdminView,
+    ConnectionPopup,
+    CleanPopup,
+    /// Statistics and session popups which only show something
+    InfoPopup,
+    Help,
+}
+
+impl Context {
+    const ALL: [Self; 10] = [
+        Self::TopicOverview,
+        Self::TopicSearch,
+        Self::Payload,
+        Self::PayloadPath,
+        Self::HistoryTable,
+        Self::AdminView,
+        Self::ConnectionPopup,
+        Self::CleanPopup,
+        Self::InfoPopup,
+        Self::Help,
+    ];
+
+    /// Name of the section in the config file
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::TopicOverview => "topics",
+            Self::TopicSearch => "topic_search",
+            Self::Payload => "payload",
+            Self::PayloadPath => "payload_path",
+            Self::HistoryTable => "history",
+            Self::AdminView => "admin",
+            Self::ConnectionPopup => "connection",
+            Self::CleanPopup => "delete_popup",
+            Self::InfoPopup => "info_popup",
+            Self::Help => "help",
+        }
+    }
+
+    pub const fn of(focus: &ElementInFocus) -> Self {
+        match focus {
+            ElementInFocus::TopicOverview => Self::TopicOverview,
+            ElementInFocus::TopicSearch => Self::TopicSearch,
+            ElementInFocus::Payload => Self::Payload,
+            ElementInFocus::PayloadPath => Self::PayloadPath,
+            ElementInFocus::HistoryTable => Self::HistoryTable,
+            ElementInFocus::AdminView => Self::AdminView,
+            ElementInFocus::ConnectionPopup => Self::ConnectionPopup,
+            ElementInFocus::CleanPopup(_) => Self::CleanPopup,
+            ElementInFocus::StatisticsPopup(_) | ElementInFocus::SessionPopup => Self::InfoPopup,
+        }
+    }
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::TopicOverview => "Topics",
+            Self::TopicSearch => "Topic search",
+            Self::Payload => "Payload",
+            Self::PayloadPath => "Payload path input",
+            Self::HistoryTable => "History",
+            Self::AdminView => "Admin space",
+            Self::ConnectionPopup => "Connection events",
+            Self::CleanPopup => "Delete keys",
+            Self::InfoPopup => "Statistics and session",
+            Self::Help => "Help",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Help,
+    Close,
+    Confirm,
+    NextFocus,
+    PreviousFocus,
+    Unselect,
+    Toggle,
+    Down,
+    Up,
+    Left,
+    Right,
+    First,
+    Last,
+    Top,
+    Bottom,
+    PageUp,
+    PageDown,
+    Search,
+    PreviousMatch,
+    NextMatch,
+    OpenMatches,
+    DeleteCharacter,
+    OpenAll,
+    CloseAll,
+    ToggleStaleOnly,
+    Statistics,
+    SessionInfo,
+    AdminSpace,
+    Connection,
+    DeleteKeys,
+    UndoDelete,
+    CopyKey,
+    CopyLatestPayload,
+    JumpToPath,
+    CopyPath,
+    CopyValue,
+    CopyPretty,
+    CopyRow,
+    ToggleRepeats,
+    UncacheEntry,
+    Refresh,
+}
+
+impl Action {
+    const ALL: [Self; 42] = [
+        Self::Quit,
+        Self::Help,
+        Self::Close,
+        Self::Confirm,
+        Self::NextFocus,
+        Self::PreviousFocus,
+        Self::Unselect,
+        Self::Toggle,
+        Self::Down,
+        Self::Up,
+        Self::Left,
+        Self::Right,
+        Self::First,
+        Self::Last,
+        Self::Top,
+        Self::Bottom,
+        Self::PageUp,
+        Self::PageDown,
+        Self::Search,
+        Self::PreviousMatch,
+        Self::NextMatch,
+        Self::OpenMatches,
+        Self::DeleteCharacter,
+        Self::OpenAll,
+        Self::CloseAll,
+        Self::ToggleStaleOnly,
+        Self::Statistics,
+        Self::SessionInfo,
+        Self::AdminSpace,
+        Self::Connection,
+        Self::DeleteKeys,
+        Self::UndoDelete,
+        Self::CopyKey,
+        Self::CopyLatestPayload,
+        Self::JumpToPath,
+        Self::CopyPath,
+        Self::CopyValue,
+        Self::CopyPretty,
+        Self::CopyRow,
+        Self::ToggleRepeats,
+        Self::UncacheEntry,
+        Self::Refresh,
+    ];
+
+    /// Name in the config file
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Help => "help",
+            Self::Close => "close",
+            Self::Confirm => "confirm",
+            Self::NextFocus => "next_focus",
+            Self::PreviousFocus => "previous_focus",
+            Self::Unselect => "unselect",
+            Self::Toggle => "toggle",
+            Self::Down => "down",
+            Self::Up => "up",
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::First => "first",
+            Self::Last => "last",
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::PageUp => "page_up",
+            Self::PageDown => "page_down",
+            Self::Search => "search",
+            Self::PreviousMatch => "previous_match",
+            Self::NextMatch => "next_match",
+            Self::OpenMatches => "open_matches",
+            Self::DeleteCharacter => "delete_character",
+            Self::OpenAll => "open_all",
+            Self::CloseAll => "close_all",
+            Self::ToggleStaleOnly => "toggle_stale_only",
+            Self::Statistics => "statistics",
+            Self::SessionInfo => "session_info",
+            Self::AdminSpace => "admin_space",
+            Self::Connection => "connection",
+            Self::DeleteKeys => "delete_keys",
+            Self::UndoDelete => "undo_delete",
+            Self::CopyKey => "copy_key",
+            Self::CopyLatestPayload => "copy_latest_payload",
+            Self::JumpToPath => "jump_to_path",
+            Self::CopyPath => "copy_path",
+            Self::CopyValue => "copy_value",
+            Self::CopyPretty => "copy_pretty",
+            Self::CopyRow => "copy_row",
+            Self::ToggleRepeats => "toggle_repeats",
+            Self::UncacheEntry => "uncache_entry",
+            Self::Refresh => "refresh",
+        }
+    }
+
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::Help => "Show the key bindings",
+            Self::Close => "Close",
+            Self::Confirm => "Confirm",
+            Self::NextFocus => "Switch focus",
+            Self::PreviousFocus => "Switch focus backwards",
+            Self::Unselect => "Clear the selection",
+            Self::Toggle => "Open or close the selection",
+            Self::Down => "Down",
+            Self::Up => "Up",
+            Self::Left => "Left or close",
+            Self::Right => "Right or open",
+            Self::First => "First (of the row for binary payloads)",
+            Self::Last => "Last (of the row for binary payloads)",
+            Self::Top => "Very first",
+            Self::Bottom => "Very last",
+            Self::PageUp => "Page up",
+            Self::PageDown => "Page down",
+            Self::Search => "Search topics",
+            Self::PreviousMatch => "Previous match",
+            Self::NextMatch => "Next match",
+            Self::OpenMatches => "Open all matches",
+            Self::DeleteCharacter => "Delete the last character",
+            Self::OpenAll => "Open all topics",
+            Self::CloseAll => "Close all topics",
+            Self::ToggleStaleOnly => "Only show stale topics or all",
+            Self::Statistics => "Statistics of the selected topic",
+            Self::SessionInfo => "Session info",
+            Self::AdminSpace => "Admin space",
+            Self::Connection => "Connection events",
+            Self::DeleteKeys => "Delete the selected key tree on Zenoh",
+            Self::UndoDelete => "Undo the last delete",
+            Self::CopyKey => "Copy the key",
+            Self::CopyLatestPayload => "Copy the raw latest payload",
+            Self::JumpToPath => "Type a path to jump to",
+            Self::CopyPath => "Copy the path of the selection",
+            Self::CopyValue => "Copy the value at the selection",
+            Self::CopyPretty => "Copy the payload as pretty JSON",
+            Self::CopyRow => "Copy the row",
+            Self::ToggleRepeats => "Hide or show repeated payloads",
+            Self::UncacheEntry => "Remove the entry from the local history (not from Zenoh)",
+            Self::Refresh => "Refresh",
+        }
+    }
+}
+
+/// Always quits, before any binding is looked up, so it can not be bound
+pub const QUIT_ALWAYS: Key = Key::ctrl('c');
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    const fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    const fn char(char: char) -> Self {
+        Self::new(KeyCode::Char(char))
+    }
+
+    const fn ctrl(char: char) -> Self {
+        Self::ctrl_key(KeyCode::Char(char))
+    }
+
+    const fn ctrl_key(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    /// Shift is part of the character (`O` instead of `o`) or the key code (`BackTab`)
+    pub fn from_event(event: &KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            modifiers: event
+                .modifiers
+                .intersection(KeyModifiers::CONTROL.union(KeyModifiers::ALT)),
+        }
+    }
+}
+
+impl std::fmt::Display for Key {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            fmt.write_str("Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            fmt.write_str("Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => fmt.write_str("Space"),
+            KeyCode::Char(char) => write!(fmt, "{char}"),
+            KeyCode::F(number) => write!(fmt, "F{number}"),
+            KeyCode::Up => fmt.write_str("↑"),
+            KeyCode::Down => fmt.write_str("↓"),
+            KeyCode::Left => fmt.write_str("←"),
+            KeyCode::Right => fmt.write_str("→"),
+            KeyCode::PageUp => fmt.write_str("PgUp"),
+            KeyCode::PageDown => fmt.write_str("PgDn"),
+            KeyCode::BackTab => fmt.write_str("Shift+Tab"),
+            KeyCode::Delete => fmt.write_str("Del"),
+            code => write!(fmt, "{code:?}"),
+        }
+    }
+}
+
+impl std::str::FromStr for Key {
+    type Err = anyhow::Error;
+
+    /// Parse keys like `q`, `Ctrl+d`, `Shift+Tab`, `PgDn` or `F5`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        // The last + is the key itself for `+` or `Ctrl++`
+        let (modifier_names, name) = match text.strip_suffix('+') {
+            Some(prefix) if prefix.is_empty() || prefix.ends_with('+') => (prefix, "+"),
+            _ => text.rsplit_once('+').unwrap_or(("", text)),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        for modifier in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => shift = true,
+                _ => anyhow::bail!("Unknown modifier {modifier} in {text}. Use Ctrl, Alt or Shift"),
+            }
+        }
+
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (None, _) => anyhow::bail!("Missing key in {text:?}"),
+            (Some('↑'), None) => KeyCode::Up,
+            (Some('↓'), None) => KeyCode::Down,
+            (Some('←'), None) => KeyCode::Left,
+            (Some('→'), None) => KeyCode::Right,
+            (Some(char), None) if shift => {
+                shift = false;
+                KeyCode::Char(char.to_ascii_uppercase())
+            }
+            (Some(char), None) => KeyCode::Char(char),
+            _ => match name.to_ascii_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" if shift => {
+                    shift = false;
+                    KeyCode::BackTab
+                }
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "del" | "delete" => KeyCode::Delete,
+                "ins" | "insert" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pgup" | "pageup" => KeyCode::PageUp,
+                "pgdn" | "pagedown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                lowercase => match lowercase.strip_prefix('f').map(str::parse) {
+                    Some(Ok(number)) => KeyCode::F(number),
+                    _ => anyhow::bail!("Unknown key {name} in {text}"),
+                },
+            },
+        };
+        // Shift is only distinguishable as part of a character or Shift+Tab
+        anyhow::ensure!(!shift, "Shift only works with characters and Tab in {text}");
+        Ok(Self { code, modifiers })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Binding {
+    pub context: Context,
+    pub key: Key,
+    pub action: Action,
+}
+
+#[derive(Clone)]
+pub struct KeyBindings {
+    bindings: Vec<Binding>,
+}
+
+impl KeyBindings {
+    pub fn action(&self, context: Context, event: &KeyEvent) -> Option<Action> {
+        let key = Key::from_event(event);
+        self.bindings
+            .iter()
+            .find(|binding| binding.context == context && binding.key == key)
+            .map(|binding| binding.action)
+    }
+
+    /// First key of the action, to be shown as a hint
+    pub fn first_key(&self, context: Context, action: Action) -> Option<Key> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.context == context && binding.action == action)
+            .map(|binding| binding.key)
+    }
+
+    /// Replace the keys of the configured actions.
+    /// The section `all` applies to every context which has the action, other sections override it.
+    pub fn with_overrides(
+        mut self,
+        sections: &BTreeMap<String, BTreeMap<String, Vec<String>>>,
+    ) -> anyhow::Result<Self> {
+        let defaults = Self::default();
+        let sections = sections
+            .get_key_value("all")
+            .into_iter()
+            .chain(sections.iter().filter(|(section, _)| *section != "all"));
+        for (section, actions) in sections {
+            let context = if section == "all" {
+                None
+            } else {
+                let context = Context::ALL
+                    .into_iter()
+                    .find(|context| context.name() == section)
+                    .ok_or_else(|| {
+                        let available = Context::ALL.map(Context::name).join(", ");
+                        anyhow::anyhow!(
+                            "Unknown section [keys.{section}]. Available: all, {available}"
+                        )
+                    })?;
+                Some(context)
+            };
+            for (name, keys) in actions {
+                let action = Action::ALL
+                    .into_iter()
+                    .find(|action| action.name() == name)
+                    .ok_or_else(|| anyhow::anyhow!("Unknown action {name} in [keys.{section}]"))?;
+                let keys = keys
+                    .iter()
+                    .map(|key| key.parse::<Key>())
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .map_err(|err| anyhow::anyhow!("[keys.{section}] {name}: {err}"))?;
+                anyhow::ensure!(
+                    !keys.contains(&QUIT_ALWAYS),
+                    "[keys.{section}] {name}: {QUIT_ALWAYS} always quits and can not be bound"
+                );
+                let contexts = context.map_or_else(
+                    || {
+                        Context::ALL
+                            .into_iter()
+                            .filter(|context| defaults.has_action(*context, action))
+                            .collect::<Vec<_>>()
+                    },
+                    |context| vec![context],
+                );
+                for context in contexts {
+                    anyhow::ensure!(
+                        defaults.has_action(context, action),
+                        "{name} is not available in [keys.{section}]. Available: {}",
+                        defaults
+                            .list(context)
+                            .iter()
+                            .map(|(action, _)| action.name())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                    self.bindings
+                        .retain(|binding| binding.context != context || binding.action != action);
+                    for key in &keys {
+                        let binding = Binding {
+                            context,
+                            key: *key,
+                            action,
+                        };
+                        // The same key listed twice is no conflict
+                        if !self.bindings.iter().any(|other| {
+                            other.context == context && other.key == *key && other.action == action
+                        }) {
+                            self.bindings.push(binding);
+                        }
+                    }
+                }
+            }
+        }
+        self.check_conflicts()?;
+        Ok(self)
+    }
+
+    fn has_action(&self, context: Context, action: Action) -> bool {
+        self.bindings
+            .iter()
+            .any(|binding| binding.context == context && binding.action == action)
+    }
+
+    fn check_conflicts(&self) -> anyhow::Result<()> {
+        for (index, binding) in self.bindings.iter().enumerate() {
+            anyhow::ensure!(
+                binding.key != QUIT_ALWAYS,
+                "Key {QUIT_ALWAYS} always quits and can not be bound to {} in [keys.{}]",
+                binding.action.name(),
+                binding.context.name()
+            );
+            if let Some(other) = self.bindings[index + 1..]
+                .iter()
+                .find(|other| other.context == binding.context && other.key == binding.key)
+            {
+                anyhow::bail!(
+                    "Key {} is bound to both {} and {} in [keys.{}]. Bind one of them to another key.",
+                    binding.key,
+                    binding.action.name(),
+                    other.action.name(),
+                    binding.context.name()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Actions of the context in the order of their first binding together with all their keys
+    pub fn list(&self, context: Context) -> Vec<(Action, Vec<Key>)> {
+        let mut list = Vec::<(Action, Vec<Key>)>::new();
+        for binding in self
+            .bindings
+            .iter()
+            .filter(|binding| binding.context == context)
+        {
+            if let Some((_, keys)) = list
+                .iter_mut()
+                .find(|(action, _)| *action == binding.action)
+            {
+                keys.push(binding.key);
+            } else {
+                list.push((binding.action, vec![binding.key]));
+            }
+        }
+        list
+    }
+}
+
+const ESC: &[Key] = &[Key::new(KeyCode::Esc)];
+const TAB: &[Key] = &[Key::new(KeyCode::Tab)];
+const BACKTAB: &[Key] = &[Key::new(KeyCode::BackTab)];
+const ENTER: &[Key] = &[Key::new(KeyCode::Enter)];
+const BACKSPACE: &[Key] = &[Key::new(KeyCode::Backspace)];
+const DELETE: &[Key] = &[Key::new(KeyCode::Delete), Key::new(KeyCode::Backspace)];
+const TOGGLE: &[Key] = &[Key::new(KeyCode::Enter), Key::char(' ')];
+const DOWN: &[Key] = &[Key::new(KeyCode::Down), Key::char('j')];
+const UP: &[Key] = &[Key::new(KeyCode::Up), Key::char('k')];
+const LEFT: &[Key] = &[Key::new(KeyCode::Left), Key::char('h')];
+const RIGHT: &[Key] = &[Key::new(KeyCode::Right), Key::char('l')];
+const HOME: &[Key] = &[Key::new(KeyCode::Home)];
+const END: &[Key] = &[Key::new(KeyCode::End)];
+const PAGE_UP: &[Key] = &[Key::new(KeyCode::PageUp), Key::ctrl('u')];
+const PAGE_DOWN: &[Key] = &[Key::new(KeyCode::PageDown), Key::ctrl('d')];
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use Action as A;
+        use Context as C;
+
+        let mut bindings = Vec::new();
+        let mut bind = |context: Context, keys: &[Key], action: Action| {
+            bindings.extend(keys.iter().map(|key| Binding {
+                context,
+                key: *key,
+                action,
+            }));
+        };
+
+        for context in [
+            C::TopicOverview,
+            C::Payload,
+            C::HistoryTable,
+            C::AdminView,
+            C::ConnectionPopup,
+        ] {
+            bind(context, &[Key::char('q')], A::Quit);
+            bind(context, &[Key::char('?')], A::Help);
+        }
+        for context in [C::TopicOverview, C::Payload, C::HistoryTable] {
+            bind(context, TAB, A::NextFocus);
+            bind(context, BACKTAB, A::PreviousFocus);
+            bind(context, ESC, A::Unselect);
+        }
+        for context in [C::TopicOverview, C::Payload, C::AdminView] {
+            bind(context, TOGGLE, A::Toggle);
+            bind(context, LEFT, A::Left);
+            bind(context, RIGHT, A::Right);
+        }
+        for context in [
+            C::TopicOverview,
+            C::Payload,
+            C::HistoryTable,
+            C::AdminView,
+            C::ConnectionPopup,
+            C::Help,
+        ] {
+            bind(context, DOWN, A::Down);
+            bind(context, UP, A::Up);
+            bind(context, HOME, A::First);
+            bind(context, PAGE_UP, A::PageUp);
+            bind(context, PAGE_DOWN, A::PageDown);
+        }
+        for context in [C::TopicOverview, C::Payload, C::HistoryTable, C::AdminView] {
+            bind(context, END, A::Last);
+        }
+
+        bind(C::TopicOverview, &[Key::char('/')], A::Search);
+        bind(C::TopicOverview, &[Key::char('o')], A::OpenAll);
+        bind(C::TopicOverview, &[Key::char('O')], A::CloseAll);
+        bind(C::TopicOverview, &[Key::char('S')], A::ToggleStaleOnly);
+        bind(C::TopicOverview, &[Key::char('s')], A::Statistics);
+        bind(C::TopicOverview, &[Key::char('i')], A::SessionInfo);
+        bind(C::TopicOverview, &[Key::char('a')], A::AdminSpace);
+        bind(C::TopicOverview, &[Key::char('c')], A::Connection);
+        bind(C::TopicOverview, DELETE, A::DeleteKeys);
+        bind(C::TopicOverview, &[Key::char('u')], A::UndoDelete);
+        bind(C::TopicOverview, &[Key::char('y')], A::CopyKey);
+        bind(C::TopicOverview, &[Key::char('Y')], A::CopyLatestPayload);
+
+        bind(C::TopicSearch, &[Key::new(KeyCode::Up)], A::PreviousMatch);
+        bind(C::TopicSearch, &[Key::new(KeyCode::Down)], A::NextMatch);
+        bind(C::TopicSearch, ENTER, A::OpenMatches);
+        bind(C::TopicSearch, BACKSPACE, A::DeleteCharacter);
+        bind(C::TopicSearch, &[Key::new(KeyCode::PageUp)], A::PageUp);
+        bind(C::TopicSearch, &[Key::new(KeyCode::PageDown)], A::PageDown);
+        bind(C::TopicSearch, TAB, A::NextFocus);
+        bind(C::TopicSearch, ESC, A::Close);
+
+        bind(C::Payload, &[Key::ctrl_key(KeyCode::Home)], A::Top);
+        bind(C::Payload, &[Key::ctrl_key(KeyCode::End)], A::Bottom);
+        bind(C::Payload, &[Key::char('.')], A::JumpToPath);
+        bind(C::Payload, &[Key::char('y')], A::CopyPath);
+        bind(C::Payload, &[Key::char('Y')], A::CopyValue);
+        bind(C::Payload, &[Key::char('p')], A::CopyPretty);
+
+        bind(C::PayloadPath, ENTER, A::Confirm);
+        bind(C::PayloadPath, BACKSPACE, A::DeleteCharacter);
+        bind(
+            C::PayloadPath,
+            &[Key::new(KeyCode::Esc), Key::new(KeyCode::Tab)],
+            A::Close,
+        );
+
+        bind(C::HistoryTable, &[Key::char('y')], A::CopyRow);
+        bind(C::HistoryTable, &[Key::char('r')], A::ToggleRepeats);
+        bind(C::HistoryTable, DELETE, A::UncacheEntry);
+
+        bind(
+            C::AdminView,
+            &[Key::new(KeyCode::Esc), Key::char('a')],
+            A::Close,
+        );
+        bind(C::AdminView, &[Key::char('r')], A::Refresh);
+
+        bind(
+            C::ConnectionPopup,
+            &[Key::new(KeyCode::Esc), Key::char('c')],
+            A::Close,
+        );
+
+        bind(C::CleanPopup, TOGGLE, A::Confirm);
+        bind(C::CleanPopup, ESC, A::Close);
+
+        bind(
+            C::InfoPopup,
+            &[
+                Key::new(KeyCode::Esc),
+                Key::new(KeyCode::Enter),
+                Key::char('q'),
+            ],
+            A::Close,
+        );
+
+        bind(
+            C::Help,
+            &[Key::new(KeyCode::Esc), Key::char('?'), Key::char('q')],
+            A::Close,
+        );
+
+        Self { bindings }
+    }
+}
+
+#[test]
+fn shift_is_part_of_the_key() {
+    let bindings = KeyBindings::default();
+    let event = KeyEvent::new(KeyCode::Char('O'), KeyModifiers::SHIFT);
+    assert_eq!(
+        bindings.action(Context::TopicOverview, &event),
+        Some(Action::CloseAll)
+    );
+    let event = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+    assert_eq!(
+        bindings.action(Context::HistoryTable, &event),
+        Some(Action::PreviousFocus)
+    );
+}
+
+#[test]
+fn control_is_distinct() {
+    let bindings = KeyBindings::default();
+    let undo = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE);
+    let page_up = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL);
+    assert_eq!(
+        bindings.action(Context::TopicOverview, &undo),
+        Some(Action::UndoDelete)
+    );
+    assert_eq!(
+        bindings.action(Context::TopicOverview, &page_up),
+        Some(Action::PageUp)
+    );
+    assert_eq!(bindings.action(Context::TopicSearch, &undo), None);
+}
+
+#[test]
+fn list_groups_keys() {
+    let list = KeyBindings::default().list(Context::CleanPopup);
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].0, Action::Confirm);
+    assert_eq!(
+        list[0]
+            .1
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        ["Enter", "Space"]
+    );
+}
+
+#[test]
+fn no_duplicate_keys_per_context() {
+    let bindings = KeyBindings::default().bindings;
+    for (index, binding) in bindings.iter().enumerate() {
+        assert!(
+            !bindings[index + 1..]
+                .iter()
+                .any(|other| other.context == binding.context && other.key == binding.key),
+            "{binding:?} is bound twice"
+        );
+    }
+}
+
+#[cfg(test)]
+fn overrides(toml: &[(&str, &str, &[&str])]) -> anyhow::Result<KeyBindings> {
+    let mut sections = BTreeMap::<String, BTreeMap<String, Vec<String>>>::new();
+    for (section, action, keys) in toml {
+        sections.entry((*section).to_owned()).or_default().insert(
+            (*action).to_owned(),
+            keys.iter().map(|key| (*key).to_owned()).collect(),
+        );
+    }
+    KeyBindings::default().with_overrides(&sections)
+}
+
+#[test]
+fn key_parses_and_displays() {
+    for text in [
+        "q",
+        "O",
+        "Ctrl+d",
+        "Alt+x",
+        "Shift+Tab",
+        "PgDn",
+        "F5",
+        "Space",
+        "Ctrl+Home",
+        "+",
+        "Ctrl++",
+    ] {
+        let key = text.parse::<Key>().unwrap();
+        assert_eq!(key.to_string(), text);
+    }
+    assert_eq!("shift+o".parse::<Key>().unwrap(), Key::char('O'));
+    assert_eq!(
+        "ctrl+U".parse::<Key>().unwrap().modifiers,
+        KeyModifiers::CONTROL
+    );
+    assert_eq!("Down".parse::<Key>().unwrap(), Key::new(KeyCode::Down));
+}
+
+#[test]
+fn key_parse_fails() {
+    assert!("".parse::<Key>().is_err());
+    assert!("Ctrl+".parse::<Key>().is_err());
+    assert!("Hyper+a".parse::<Key>().is_err());
+    assert!("Shift+Home".parse::<Key>().is_err());
+    assert!("Foo".parse::<Key>().is_err());
+}
+
+#[test]
+fn override_replaces_default_keys() {
+    let bindings = overrides(&[("topics", "search", &["f"])]).unwrap();
+    let slash = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE);
+    let f = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE);
+    assert_eq!(bindings.action(Context::TopicOverview, &slash), None);
+    assert_eq!(
+        bindings.action(Context::TopicOverview, &f),
+        Some(Action::Search)
+    );
+}
+
+#[test]
+fn section_overrides_all() {
+    let bindings = overrides(&[("all", "quit", &["Ctrl+q"]), ("payload", "quit", &["x"])]).unwrap();
+    assert_eq!(
+        bindings.first_key(Context::TopicOverview, Action::Quit),
+        Some(Key::ctrl('q'))
+    );
+    assert_eq!(
+        bindings.first_key(Context::AdminView, Action::Quit),
+        Some(Key::ctrl('q'))
+    );
+    assert_eq!(
+        bindings.first_key(Context::Payload, Action::Quit),
+        Some(Key::char('x'))
+    );
+}
+
+#[test]
+fn override_errors() {
+    assert!(overrides(&[("nowhere", "quit", &["x"])]).is_err());
+    assert!(overrides(&[("topics", "fly", &["x"])]).is_err());
+    assert!(overrides(&[("topic_search", "quit", &["x"])]).is_err());
+    let conflict = overrides(&[("topics", "quit", &["j"])]).err().unwrap();
+    assert_eq!(
+        conflict.to_string(),
+        "Key j is bound to both down and quit in [keys.topics]. Bind one of them to another key."
+    );
+}
+
+#[test]
+fn ctrl_c_can_not_be_bound() {
+    let err = overrides(&[("all", "quit", &["Ctrl+c"])]).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "[keys.all] quit: Ctrl+c always quits and can not be bound"
+    );
+    assert!(overrides(&[("topics", "search", &["/", "Ctrl+c"])]).is_err());
+    assert!(KeyBindings::default().check_conflicts().is_ok());
+}
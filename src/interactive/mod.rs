@@ -1,8 +1,6 @@
 use std::time::{Duration, Instant};
 
-use crossterm::event::{
-    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
-};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEventKind};
 use ratatui::backend::{Backend, CrosstermBackend};
 use ratatui::layout::{Alignment, Position, Rect};
 use ratatui::text::Span;
@@ -12,14 +10,29 @@ use std::sync::Arc;
 
 use zenoh::Session;
 
+use self::keybindings::{Action, Context, Key, KeyBindings};
 use self::ui::ElementInFocus;
+use self::zenoh_history::LatestPayload;
 use crate::zenoh_client::SessionInfo;
-use crate::payload::Payload;
+use crate::config_file::KeyBindingSections;
+use crate::format;
+use crate::payload::{JsonSelector, Payload};
+use crate::rules::Rules;
+use crate::staleness::StaleSettings;
+use crate::statistics::Statistics;
 
+mod admin_view;
 mod clean;
+mod clipboard;
 mod details;
 mod footer;
+mod help_popup;
+mod keybindings;
 mod connection_error_widget;
+mod connection_popup;
+mod session_popup;
+mod statistics_popup;
+mod theme;
 mod zenoh_history;
 mod zenoh_thread;
 mod topic_overview;
@@ -58,15 +71,32 @@ fn reset_terminal() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn show(
     session: Arc<Session>,
     session_info: &SessionInfo,
     subscribe_keyexpr: Vec<String>,
     payload_size_limit: usize,
+    retention: Option<usize>,
+    gap_threshold: Duration,
+    stale_settings: StaleSettings,
+    rules: Option<Rules>,
+    keys: &KeyBindingSections,
+    theme_config: Option<&toml::Value>,
 ) -> anyhow::Result<()> {
-    let zenoh_thread =
-        zenoh_thread::ZenohThread::new(session, subscribe_keyexpr, payload_size_limit)?;
-    let app = App::new(session_info, zenoh_thread);
+    let keybindings = KeyBindings::default()
+        .with_overrides(keys)
+        .map_err(|err| anyhow::anyhow!("Invalid key bindings in the config file: {err}"))?;
+    theme::init(theme::Theme::from_env(theme_config)?);
+    let zenoh_thread = zenoh_thread::ZenohThread::new(
+        session,
+        subscribe_keyexpr,
+        payload_size_limit,
+        retention,
+        stale_settings,
+        rules,
+    )?;
+    let app = App::new(session_info, zenoh_thread, keybindings, gap_threshold);
 
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic| {
@@ -146,21 +176,40 @@ where
 }
 
 pub struct App {
+    admin_view: admin_view::AdminView,
+    connection_popup: connection_popup::ConnectionPopup,
     details: details::Details,
     focus: ElementInFocus,
     footer: footer::Footer,
+    /// Used by the statistics popup
+    gap_threshold: Duration,
+    help_popup: Option<help_popup::HelpPopup>,
+    keybindings: KeyBindings,
     zenoh_thread: zenoh_thread::ZenohThread,
     topic_overview: topic_overview::TopicOverview,
+    /// Latest payloads of the keys deleted in this session, newest last
+    undo: Vec<Vec<(String, LatestPayload)>>,
 }
 
 impl App {
-    fn new(session_info: &SessionInfo, zenoh_thread: zenoh_thread::ZenohThread) -> Self {
+    fn new(
+        session_info: &SessionInfo,
+        zenoh_thread: zenoh_thread::ZenohThread,
+        keybindings: KeyBindings,
+        gap_threshold: Duration,
+    ) -> Self {
         Self {
+            admin_view: admin_view::AdminView::default(),
+            connection_popup: connection_popup::ConnectionPopup::default(),
             details: details::Details::default(),
             focus: ElementInFocus::TopicOverview,
             footer: footer::Footer::new(session_info),
+            gap_threshold,
+            help_popup: None,
+            keybindings,
             zenoh_thread,
             topic_overview: topic_overview::TopicOverview::default(),
+            undo: Vec::new(),
         }
     }
 
@@ -179,7 +228,7 @@ impl App {
             .get_history()
             .get(&topic)
             .and_then(|entries| {
-                let index = self.details.selected_history_index(entries.len());
+                let index = self.details.selected_history_index(entries);
                 entries.get(index)
             })
             .is_some_and(|entry| {
@@ -190,6 +239,23 @@ impl App {
             })
     }
 
+    /// The typed path when it exists in the selected payload
+    fn get_typed_payload_path(&self) -> Option<Vec<JsonSelector>> {
+        let path = JsonSelector::parse_path(&self.details.payload.path_input).ok()?;
+        self.get_selected_payload()?.select(&path)?;
+        Some(path)
+    }
+
+    /// The history table rows are used by several lookups per draw and event, calculate them once
+    fn update_details_rows(&mut self) {
+        let topic = self.topic_overview.get_selected();
+        let history = self.zenoh_thread.get_history();
+        let entries = topic
+            .and_then(|topic| history.get(&topic))
+            .map_or(&[][..], Vec::as_slice);
+        self.details.update_rows(entries);
+    }
+
     /// On current topic with the current history table index
     fn get_selected_payload(&self) -> Option<Payload> {
         let topic = self.topic_overview.get_selected()?;
@@ -197,58 +263,118 @@ impl App {
             .get_history()
             .get(&topic)
             .and_then(|entries| {
-                let index = self.details.selected_history_index(entries.len());
+                let index = self.details.selected_history_index(entries);
                 entries.get(index)
             })
             .map(|entry| entry.payload.clone())
     }
 
+    /// Untruncated payload of the latest put on the current topic
+    fn get_latest_raw_payload(&self) -> Option<Vec<u8>> {
+        let topic = self.topic_overview.get_selected()?;
+        self.zenoh_thread
+            .get_history()
+            .get_latest(&topic)
+            .map(|latest| latest.payload.clone())
+    }
+
+    /// Selected history table row as tab separated time, kind and payload
+    fn get_selected_history_row(&self) -> Option<String> {
+        let topic = self.topic_overview.get_selected()?;
+        let history = self.zenoh_thread.get_history();
+        let entries = history.get(&topic)?;
+        let entry = entries.get(self.details.selected_history_index(entries))?;
+        Some(format!(
+            "{}\t{}\t{}",
+            entry.time,
+            format::kind(entry.kind),
+            entry.payload
+        ))
+    }
+
     #[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+    /// Copy via OSC 52 and show the outcome in the footer, a failed copy does not end the TUI
+    fn copy(&mut self, what: &str, data: impl AsRef<[u8]>) {
+        match clipboard::copy(data) {
+            Ok(()) => self.footer.show_message(format!("Copied {what}"), false),
+            Err(err) => self
+                .footer
+                .show_message(format!("Failed to copy {what}: {err}"), true),
+        }
+    }
+
     fn on_key(&mut self, key: KeyEvent) -> anyhow::Result<Refresh> {
-        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        if Key::from_event(&key) == keybindings::QUIT_ALWAYS {
             return Ok(Refresh::Quit);
         }
+        self.update_details_rows();
+
+        if let Some(help) = &mut self.help_popup {
+            let before = help.scroll;
+            match self.keybindings.action(Context::Help, &key) {
+                Some(Action::Close) => {
+                    self.help_popup = None;
+                    return Ok(Refresh::Update);
+                }
+                Some(Action::Down) => help.scroll = help.scroll.saturating_add(1),
+                Some(Action::Up) => help.scroll = help.scroll.saturating_sub(1),
+                Some(Action::PageDown) => help.scroll = help.scroll.saturating_add(10),
+                Some(Action::PageUp) => help.scroll = help.scroll.saturating_sub(10),
+                Some(Action::First) => help.scroll = 0,
+                _ => {}
+            }
+            help.scroll = help.scroll.min(help.max_scroll);
+            return Ok(if help.scroll == before {
+                Refresh::Skip
+            } else {
+                Refresh::Update
+            });
+        }
+
+        let context = Context::of(&self.focus);
+        let action = self.keybindings.action(context, &key);
+        match action {
+            Some(Action::Quit) => return Ok(Refresh::Quit),
+            Some(Action::Help) => {
+                self.help_popup = Some(help_popup::HelpPopup::new(context));
+                return Ok(Refresh::Update);
+            }
+            _ => {}
+        }
 
         let update = match &self.focus {
-            ElementInFocus::TopicOverview => match key.code {
-                KeyCode::Char('q') => return Ok(Refresh::Quit),
-                KeyCode::Tab if self.can_switch_to_payload() => {
+            ElementInFocus::TopicOverview => match action {
+                Some(Action::NextFocus) if self.can_switch_to_payload() => {
                     self.focus = ElementInFocus::Payload;
                     true
                 }
-                KeyCode::Tab | KeyCode::BackTab if self.can_switch_to_history_table() => {
+                Some(Action::NextFocus | Action::PreviousFocus)
+                    if self.can_switch_to_history_table() =>
+                {
                     self.focus = ElementInFocus::HistoryTable;
                     true
                 }
-                KeyCode::Char('/') => {
+                Some(Action::Search) => {
                     self.focus = ElementInFocus::TopicSearch;
                     true
                 }
-                KeyCode::Esc => self.topic_overview.state.select(vec![]),
-                KeyCode::Enter | KeyCode::Char(' ') => self.topic_overview.state.toggle_selected(),
-                KeyCode::Down | KeyCode::Char('j') => self.topic_overview.state.key_down(),
-                KeyCode::Up | KeyCode::Char('k') => self.topic_overview.state.key_up(),
-                KeyCode::Left | KeyCode::Char('h') => self.topic_overview.state.key_left(),
-                KeyCode::Right | KeyCode::Char('l') => self.topic_overview.state.key_right(),
-                KeyCode::Home => self.topic_overview.state.select_first(),
-                KeyCode::End => self.topic_overview.state.select_last(),
-                KeyCode::PageUp => {
+                Some(Action::Unselect) => self.topic_overview.state.select(vec![]),
+                Some(Action::Toggle) => self.topic_overview.state.toggle_selected(),
+                Some(Action::Down) => self.topic_overview.state.key_down(),
+                Some(Action::Up) => self.topic_overview.state.key_up(),
+                Some(Action::Left) => self.topic_overview.state.key_left(),
+                Some(Action::Right) => self.topic_overview.state.key_right(),
+                Some(Action::First) => self.topic_overview.state.select_first(),
+                Some(Action::Last) => self.topic_overview.state.select_last(),
+                Some(Action::PageUp) => {
                     let page_jump = (self.topic_overview.last_area.height / 3) as usize;
                     self.topic_overview.state.scroll_up(page_jump)
                 }
-                KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    let page_jump = (self.topic_overview.last_area.height / 3) as usize;
-                    self.topic_overview.state.scroll_up(page_jump)
-                }
-                KeyCode::PageDown => {
-                    let page_jump = (self.topic_overview.last_area.height / 3) as usize;
-                    self.topic_overview.state.scroll_down(page_jump)
-                }
-                KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Action::PageDown) => {
                     let page_jump = (self.topic_overview.last_area.height / 3) as usize;
                     self.topic_overview.state.scroll_down(page_jump)
                 }
-                KeyCode::Backspace | KeyCode::Delete => {
+                Some(Action::DeleteKeys) => {
                     if let Some(topic) = self.topic_overview.get_selected() {
                         self.focus = ElementInFocus::CleanPopup(topic);
                         true
@@ -256,178 +382,337 @@ impl App {
                         false
                     }
                 }
-                KeyCode::Char('o') => {
+                Some(Action::UndoDelete) => {
+                    if let Some(snapshot) = self.undo.last() {
+                        // Keep the snapshot to retry when publishing failed
+                        match self.zenoh_thread.restore(snapshot) {
+                            Ok(()) => {
+                                self.undo.pop();
+                            }
+                            Err(err) => self
+                                .zenoh_thread
+                                .record_error(format!("Failed to undo the delete: {err}")),
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                Some(Action::OpenAll) => {
                     self.topic_overview.search.clear();
                     self.open_all_search_matches()
                 }
-                KeyCode::Char('O') => self.topic_overview.state.close_all(),
+                Some(Action::CloseAll) => self.topic_overview.state.close_all(),
+                Some(Action::Statistics) => {
+                    if let Some(topic) = self.topic_overview.get_selected() {
+                        self.focus = ElementInFocus::StatisticsPopup(topic);
+                        true
+                    } else {
+                        false
+                    }
+                }
+                Some(Action::ToggleStaleOnly) => {
+                    self.topic_overview.stale_only = !self.topic_overview.stale_only;
+                    true
+                }
+                Some(Action::SessionInfo) => {
+                    self.focus = ElementInFocus::SessionPopup;
+                    true
+                }
+                Some(Action::AdminSpace) => {
+                    self.admin_view.refresh(self.zenoh_thread.session());
+                    self.focus = ElementInFocus::AdminView;
+                    true
+                }
+                Some(Action::Connection) => {
+                    self.connection_popup.scroll = 0;
+                    self.focus = ElementInFocus::ConnectionPopup;
+                    true
+                }
+                Some(Action::CopyKey) => {
+                    if let Some(topic) = self.topic_overview.get_selected() {
+                        self.copy("key", topic);
+                    }
+                    true
+                }
+                Some(Action::CopyLatestPayload) => {
+                    if let Some(payload) = self.get_latest_raw_payload() {
+                        self.copy("payload", payload);
+                    }
+                    true
+                }
                 _ => false,
             },
-            ElementInFocus::TopicSearch => match key.code {
-                KeyCode::Char(char) => {
-                    self.topic_overview.search += &char.to_lowercase().to_string();
-                    self.search_select(SearchSelection::Stay);
-                    true // Render new because of search string change
-                }
-                KeyCode::Backspace => {
+            ElementInFocus::TopicSearch => match action {
+                Some(Action::DeleteCharacter) => {
                     self.topic_overview.search.pop();
                     self.search_select(SearchSelection::Stay);
                     true // Render new because of search string change
                 }
-                KeyCode::Up => self.search_select(SearchSelection::Before),
-                KeyCode::Down => self.search_select(SearchSelection::After),
-                KeyCode::Enter => {
+                Some(Action::PreviousMatch) => self.search_select(SearchSelection::Before),
+                Some(Action::NextMatch) => self.search_select(SearchSelection::After),
+                Some(Action::OpenMatches) => {
                     let selection_changed = self.search_select(SearchSelection::After);
                     self.topic_overview.state.close_all(); // Exclusively open search matches
                     let open_changed = self.open_all_search_matches();
                     selection_changed || open_changed
                 }
-                KeyCode::Esc => {
+                Some(Action::Close) => {
                     self.topic_overview.search = String::new();
                     self.focus = ElementInFocus::TopicOverview;
                     true
                 }
-                KeyCode::PageUp => {
+                Some(Action::PageUp) => {
                     let page_jump = (self.topic_overview.last_area.height / 3) as usize;
                     self.topic_overview.state.scroll_up(page_jump)
                 }
-                KeyCode::PageDown => {
+                Some(Action::PageDown) => {
                     let page_jump = (self.topic_overview.last_area.height / 3) as usize;
                     self.topic_overview.state.scroll_down(page_jump)
                 }
-                KeyCode::Tab => {
+                Some(Action::NextFocus) => {
                     self.focus = ElementInFocus::TopicOverview;
                     true
                 }
-                _ => false,
-            },
-            ElementInFocus::Payload => {
-                if key.code == KeyCode::Char('q') {
-                    return Ok(Refresh::Quit);
+                Some(_) => false,
+                // Unbound keys are typed into the search
+                None => {
+                    if let KeyCode::Char(char) = key.code {
+                        self.topic_overview.search += &char.to_lowercase().to_string();
+                        self.search_select(SearchSelection::Stay);
+                        true // Render new because of search string change
+                    } else {
+                        false
+                    }
                 }
-                if matches!(key.code, KeyCode::Tab) && self.can_switch_to_history_table() {
+            },
+            ElementInFocus::Payload => match action {
+                Some(Action::NextFocus) if self.can_switch_to_history_table() => {
                     self.focus = ElementInFocus::HistoryTable;
-                    return Ok(Refresh::Update);
+                    true
                 }
-                if matches!(key.code, KeyCode::Tab | KeyCode::BackTab) {
+                Some(Action::NextFocus | Action::PreviousFocus) => {
                     self.focus = ElementInFocus::TopicOverview;
-                    return Ok(Refresh::Update);
+                    true
                 }
-                match self.get_selected_payload() {
-                    Some(Payload::Binary(_)) => match key.code {
-                        KeyCode::Esc => self.details.payload.binary_state.select_address(None),
-                        KeyCode::Down | KeyCode::Char('j') => {
-                            self.details.payload.binary_state.key_down()
-                        }
-                        KeyCode::Up | KeyCode::Char('k') => {
-                            self.details.payload.binary_state.key_up()
+                Some(action) => match self.get_selected_payload() {
+                    Some(Payload::Binary(_)) => {
+                        let state = &mut self.details.payload.binary_state;
+                        match action {
+                            Action::Unselect => state.select_address(None),
+                            Action::Down => state.key_down(),
+                            Action::Up => state.key_up(),
+                            Action::Left => state.key_left(),
+                            Action::Right => state.key_right(),
+                            Action::Top => state.select_address(Some(0)),
+                            Action::Bottom => state.select_address(Some(usize::MAX)),
+                            Action::First => state.select_first_in_row(),
+                            Action::Last => state.select_last_in_row(),
+                            Action::PageUp => state.scroll_up(3),
+                            Action::PageDown => state.scroll_down(3),
+                            _ => false,
                         }
-                        KeyCode::Left | KeyCode::Char('h') => {
-                            self.details.payload.binary_state.key_left()
-                        }
-                        KeyCode::Right | KeyCode::Char('l') => {
-                            self.details.payload.binary_state.key_right()
-                        }
-                        KeyCode::Home if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            self.details.payload.binary_state.select_address(Some(0))
-                        }
-                        KeyCode::End if key.modifiers.contains(KeyModifiers::CONTROL) => self
-                            .details
-                            .payload
-                            .binary_state
-                            .select_address(Some(usize::MAX)),
-                        KeyCode::Home => self.details.payload.binary_state.select_first_in_row(),
-                        KeyCode::End => self.details.payload.binary_state.select_last_in_row(),
-                        KeyCode::PageUp => self.details.payload.binary_state.scroll_up(3),
-                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            self.details.payload.binary_state.scroll_up(3)
-                        }
-                        KeyCode::PageDown => self.details.payload.binary_state.scroll_down(3),
-                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            self.details.payload.binary_state.scroll_down(3)
-                        }
-                        _ => false,
-                    },
-                    Some(Payload::Json(_) | Payload::MessagePack(_)) => match key.code {
-                        KeyCode::Esc => self.details.payload.json_state.select(vec![]),
-                        KeyCode::Enter | KeyCode::Char(' ') => {
-                            self.details.payload.json_state.toggle_selected()
-                        }
-                        KeyCode::Down | KeyCode::Char('j') => {
-                            self.details.payload.json_state.key_down()
+                    }
+                    Some(Payload::Json(_) | Payload::MessagePack(_)) => match action {
+                        Action::Unselect => self.details.payload.json_state.select(vec![]),
+                        Action::Toggle => self.details.payload.json_state.toggle_selected(),
+                        Action::Down => self.details.payload.json_state.key_down(),
+                        Action::Up => self.details.payload.json_state.key_up(),
+                        Action::Left => self.details.payload.json_state.key_left(),
+                        Action::Right => self.details.payload.json_state.key_right(),
+                        Action::First | Action::Top => {
+                            self.details.payload.json_state.select_first()
                         }
-                        KeyCode::Up | KeyCode::Char('k') => {
-                            self.details.payload.json_state.key_up()
+                        Action::Last | Action::Bottom => {
+                            self.details.payload.json_state.select_last()
                         }
-                        KeyCode::Left | KeyCode::Char('h') => {
-                            self.details.payload.json_state.key_left()
+                        Action::PageUp => self.details.payload.json_state.scroll_up(3),
+                        Action::PageDown => self.details.payload.json_state.scroll_down(3),
+                        Action::JumpToPath => {
+                            let selected = self.details.payload.json_state.selected();
+                            self.details.payload.path_input = JsonSelector::format_path(selected);
+                            self.focus = ElementInFocus::PayloadPath;
+                            true
                         }
-                        KeyCode::Right | KeyCode::Char('l') => {
-                            self.details.payload.json_state.key_right()
+                        Action::CopyPath => {
+                            let path = JsonSelector::format_path(
+                                self.details.payload.json_state.selected(),
+                            );
+                            self.copy("path", path);
+                            true
                         }
-                        KeyCode::Home => self.details.payload.json_state.select_first(),
-                        KeyCode::End => self.details.payload.json_state.select_last(),
-                        KeyCode::PageUp => self.details.payload.json_state.scroll_up(3),
-                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            self.details.payload.json_state.scroll_up(3)
+                        Action::CopyValue => {
+                            let selected = self.details.payload.json_state.selected();
+                            if let Some(value) = self
+                                .get_selected_payload()
+                                .and_then(|payload| payload.select(selected))
+                            {
+                                self.copy("value", format!("{:#}", Payload::selected(value)));
+                            }
+                            true
                         }
-                        KeyCode::PageDown => self.details.payload.json_state.scroll_down(3),
-                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            self.details.payload.json_state.scroll_down(3)
+                        Action::CopyPretty => {
+                            if let Some(payload) = self.get_selected_payload() {
+                                match serde_json::to_string_pretty(&payload) {
+                                    Ok(pretty) => self.copy("pretty payload", pretty),
+                                    Err(err) => self.footer.show_message(
+                                        format!("Failed to format the payload: {err}"),
+                                        true,
+                                    ),
+                                }
+                            }
+                            true
                         }
                         _ => false,
                     },
                     Some(Payload::String(_)) | None => false,
+                },
+                None => false,
+            },
+            ElementInFocus::PayloadPath => match action {
+                Some(Action::DeleteCharacter) => {
+                    self.details.payload.path_input.pop();
+                    true
                 }
-            }
-            ElementInFocus::HistoryTable => match key.code {
-                KeyCode::Char('q') => return Ok(Refresh::Quit),
-                KeyCode::BackTab if self.can_switch_to_payload() => {
+                Some(Action::Confirm) => {
+                    if let Some(path) = self.get_typed_payload_path() {
+                        let state = &mut self.details.payload.json_state;
+                        for i in 0..path.len() {
+                            state.open(path[0..i].to_vec());
+                        }
+                        state.select(path);
+                        self.focus = ElementInFocus::Payload;
+                    }
+                    true
+                }
+                Some(Action::Close) => {
                     self.focus = ElementInFocus::Payload;
                     true
                 }
-                KeyCode::Tab | KeyCode::BackTab => {
+                Some(_) => false,
+                // Unbound keys are typed into the path
+                None => {
+                    if let KeyCode::Char(char) = key.code {
+                        self.details.payload.path_input.push(char);
+                        true
+                    } else {
+                        false
+                    }
+                }
+            },
+            ElementInFocus::HistoryTable => match action {
+                Some(Action::PreviousFocus) if self.can_switch_to_payload() => {
+                    self.focus = ElementInFocus::Payload;
+                    true
+                }
+                Some(Action::NextFocus | Action::PreviousFocus) => {
                     self.focus = ElementInFocus::TopicOverview;
                     true
                 }
-                KeyCode::Esc => self.details.table_state.select(None),
-                KeyCode::Down | KeyCode::Char('j') => self.details.table_state.select_next(),
-                KeyCode::Up | KeyCode::Char('k') => self.details.table_state.select_previous(),
-                KeyCode::Home => self.details.table_state.select_first(),
-                KeyCode::End => self.details.table_state.select_last(),
-                KeyCode::PageUp => self.details.table_state.scroll_up_by(3),
-                KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.details.table_state.scroll_up_by(3)
-                }
-                KeyCode::PageDown => self.details.table_state.scroll_down_by(3),
-                KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.details.table_state.scroll_down_by(3)
-                }
-                KeyCode::Delete | KeyCode::Backspace => {
-                    // This is a more hidden feature as changing the cached history might be weird to understand.
+                Some(Action::Unselect) => self.details.table_state.select(None),
+                Some(Action::Down) => self.details.table_state.select_next(),
+                Some(Action::Up) => self.details.table_state.select_previous(),
+                Some(Action::First) => self.details.table_state.select_first(),
+                Some(Action::Last) => self.details.table_state.select_last(),
+                Some(Action::PageUp) => self.details.table_state.scroll_up_by(3),
+                Some(Action::PageDown) => self.details.table_state.scroll_down_by(3),
+                Some(Action::CopyRow) => {
+                    if let Some(row) = self.get_selected_history_row() {
+                        self.copy("row", row);
+                    }
+                    true
+                }
+                Some(Action::ToggleRepeats) => {
+                    let topic = self
+                        .topic_overview
+                        .get_selected()
+                        .expect("Should have a selected topic when on history view");
+                    if let Some(entries) = self.zenoh_thread.get_history().get(&topic) {
+                        self.details.toggle_changes_only(entries);
+                    }
+                    true
+                }
+                Some(Action::UncacheEntry) => {
                     // Delete keys != Remove from local cache.
-                    // Therefore, its not visible in the footer.
-                    if let Some(selection) = self.details.table_state.selected() {
+                    if self.details.table_state.selected().is_some() {
                         let topic = self
                             .topic_overview
                             .get_selected()
                             .expect("Should have a selected topic when on history view");
-                        self.zenoh_thread
-                            .uncache_topic_entry(&topic, selection)
-                            .is_some()
+                        let index = self
+                            .zenoh_thread
+                            .get_history()
+                            .get(&topic)
+                            .map(|entries| self.details.selected_history_index(entries));
+                        index.is_some_and(|index| {
+                            self.zenoh_thread
+                                .uncache_topic_entry(&topic, index)
+                                .is_some()
+                        })
                     } else {
                         false
                     }
                 }
                 _ => false,
             },
+            ElementInFocus::AdminView => match action {
+                Some(Action::Close) => {
+                    self.focus = ElementInFocus::TopicOverview;
+                    true
+                }
+                Some(Action::Refresh) => {
+                    self.admin_view.refresh(self.zenoh_thread.session());
+                    true
+                }
+                Some(Action::Toggle) => self.admin_view.state.toggle_selected(),
+                Some(Action::Down) => self.admin_view.state.key_down(),
+                Some(Action::Up) => self.admin_view.state.key_up(),
+                Some(Action::Left) => self.admin_view.state.key_left(),
+                Some(Action::Right) => self.admin_view.state.key_right(),
+                Some(Action::First) => self.admin_view.state.select_first(),
+                Some(Action::Last) => self.admin_view.state.select_last(),
+                Some(Action::PageUp) => self.admin_view.state.scroll_up(3),
+                Some(Action::PageDown) => self.admin_view.state.scroll_down(3),
+                _ => false,
+            },
+            ElementInFocus::ConnectionPopup => {
+                let scroll = &mut self.connection_popup.scroll;
+                let before = *scroll;
+                match action {
+                    Some(Action::Close) => {
+                        self.focus = ElementInFocus::TopicOverview;
+                        return Ok(Refresh::Update);
+                    }
+                    Some(Action::Down) => *scroll = scroll.saturating_add(1),
+                    Some(Action::Up) => *scroll = scroll.saturating_sub(1),
+                    Some(Action::PageDown) => *scroll = scroll.saturating_add(10),
+                    Some(Action::PageUp) => *scroll = scroll.saturating_sub(10),
+                    Some(Action::First) => *scroll = 0,
+                    _ => {}
+                }
+                *scroll = (*scroll).min(self.connection_popup.max_scroll);
+                *scroll != before
+            }
             ElementInFocus::CleanPopup(topic) => {
-                if matches!(key.code, KeyCode::Enter | KeyCode::Char(' ')) {
+                if action == Some(Action::Confirm) {
+                    let snapshot = self.zenoh_thread.snapshot_below(topic);
+                    if !snapshot.is_empty() {
+                        self.undo.push(snapshot);
+                    }
                     self.zenoh_thread.clean_below(topic)?;
                 }
+                // Any other key aborts
                 self.focus = ElementInFocus::TopicOverview;
                 true
             }
+            ElementInFocus::StatisticsPopup(_) | ElementInFocus::SessionPopup => {
+                if action == Some(Action::Close) {
+                    self.focus = ElementInFocus::TopicOverview;
+                    true
+                } else {
+                    false
+                }
+            }
         };
         Ok(if update {
             Refresh::Update
@@ -438,8 +723,17 @@ impl App {
 
     fn on_scroll(&mut self, direction: ScrollDirection, column: u16, row: u16) -> Refresh {
         let position = Position { x: column, y: row };
+        self.update_details_rows();
 
-        let changed = if self.topic_overview.last_area.contains(position) {
+        let changed = if matches!(self.focus, ElementInFocus::AdminView) {
+            if !self.admin_view.last_area.contains(position) {
+                return Refresh::Skip;
+            }
+            match direction {
+                ScrollDirection::Up => self.admin_view.state.scroll_up(1),
+                ScrollDirection::Down => self.admin_view.state.scroll_down(1),
+            }
+        } else if self.topic_overview.last_area.contains(position) {
             match direction {
                 ScrollDirection::Up => self.topic_overview.state.scroll_up(1),
                 ScrollDirection::Down => self.topic_overview.state.scroll_down(1),
@@ -479,6 +773,15 @@ impl App {
 
     fn on_click(&mut self, column: u16, row: u16) -> Refresh {
         let position = Position::new(column, row);
+        self.update_details_rows();
+
+        if matches!(self.focus, ElementInFocus::AdminView) {
+            return if self.admin_view.state.click_at(position) {
+                Refresh::Update
+            } else {
+                Refresh::Skip
+            };
+        }
 
         if let Some(identifier) = self.topic_overview.state.rendered_at(position) {
             let is_already_selected = identifier == self.topic_overview.state.selected();
@@ -600,7 +903,9 @@ impl App {
         const HEADER_HEIGHT: u16 = 1;
         const FOOTER_HEIGHT: u16 = 1;
 
+        self.update_details_rows();
         let connection_error = self.zenoh_thread.has_connection_err();
+        let alerts = self.zenoh_thread.get_alerts();
 
         let area = frame.size();
         let Rect { width, height, .. } = area;
@@ -625,19 +930,41 @@ impl App {
                 .saturating_sub(error_height),
             ..area
         };
+        #[allow(clippy::cast_possible_truncation)]
+        let alerts_height = if alerts.is_empty() {
+            0
+        } else {
+            alerts.len().min(3) as u16 + 1
+        };
+        let alerts_area = Rect {
+            height: alerts_height,
+            y: error_area.y.saturating_sub(alerts_height),
+            ..area
+        };
         let main_area = Rect {
             height: height
                 .saturating_sub(HEADER_HEIGHT + FOOTER_HEIGHT)
-                .saturating_sub(error_height),
+                .saturating_sub(error_height)
+                .saturating_sub(alerts_height),
             y: HEADER_HEIGHT,
             ..area
         };
 
-        if let Some(topic) = self.topic_overview.get_selected() {
+        if matches!(self.focus, ElementInFocus::AdminView) {
+            let paragraph = Paragraph::new(Span::styled("Zenoh Admin Space", ui::STYLE_BOLD));
+            frame.render_widget(paragraph.alignment(Alignment::Center), header_area);
+        } else if let Some(topic) = self.topic_overview.get_selected() {
             let paragraph = Paragraph::new(Span::styled(topic, ui::STYLE_BOLD));
             frame.render_widget(paragraph.alignment(Alignment::Center), header_area);
         }
 
+        {
+            let monitor = self.zenoh_thread.get_connection_monitor();
+            let status = connection_popup::status(&monitor, Instant::now());
+            let paragraph = Paragraph::new(status).alignment(Alignment::Right);
+            frame.render_widget(paragraph, header_area);
+        }
+
         self.footer.draw(frame, footer_area, self);
         if let Some(connection_error) = connection_error {
             connection_error_widget::draw(
@@ -647,6 +974,20 @@ impl App {
                 &connection_error,
             );
         }
+        if !alerts.is_empty() {
+            let title = format!("Alerts ({})", alerts.len());
+            // Newest alerts first as not all of them might fit
+            let text = alerts.into_iter().rev().collect::<Vec<_>>().join("\n");
+            connection_error_widget::draw(frame, alerts_area, &title, &text);
+        }
+
+        if matches!(self.focus, ElementInFocus::AdminView) {
+            self.admin_view.draw(frame, main_area);
+            if let Some(help) = &mut self.help_popup {
+                help.draw(frame, &self.keybindings);
+            }
+            return;
+        }
 
         let history = self.zenoh_thread.get_history();
 
@@ -681,8 +1022,34 @@ impl App {
         );
         drop(history);
 
-        if let ElementInFocus::CleanPopup(topic) = &self.focus {
-            clean::draw_popup(frame, topic);
+        match &self.focus {
+            ElementInFocus::CleanPopup(topic) => {
+                clean::draw_popup(frame, topic, &self.keybindings);
+            }
+            ElementInFocus::StatisticsPopup(topic) => {
+                let observations = self
+                    .zenoh_thread
+                    .get_history()
+                    .get_observations_below(topic);
+                let statistics = Statistics::calculate(
+                    &observations,
+                    chrono::Local::now().naive_local(),
+                    self.gap_threshold,
+                );
+                statistics_popup::draw(frame, topic, &statistics);
+            }
+            ElementInFocus::SessionPopup => {
+                session_popup::draw(frame, &self.zenoh_thread.get_session_status());
+            }
+            ElementInFocus::ConnectionPopup => {
+                let monitor = self.zenoh_thread.get_connection_monitor();
+                self.connection_popup.draw(frame, &monitor);
+            }
+            _ => {}
+        }
+
+        if let Some(help) = &mut self.help_popup {
+            help.draw(frame, &self.keybindings);
         }
     }
 }
@@ -0,0 +1,21 @@
+use ratatui::layout::Alignment;
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::interactive::theme;
+use crate::interactive::ui::popup_area;
+use crate::zenoh_client::SessionStatus;
+
+pub fn draw(frame: &mut Frame, status: &SessionStatus) {
+    let block = Block::bordered()
+        .border_style(theme::get().focus)
+        .title_alignment(Alignment::Center)
+        .title(format!("Session ({})", status.summary()));
+    let text = status.lines().into_iter().map(Line::raw).collect::<Text>();
+    let width = text.width().max(30).saturating_add(4);
+    let area = popup_area(frame.size(), width, text.height().saturating_add(2));
+    let paragraph = Paragraph::new(text).block(block);
+    frame.render_widget(Clear, area); // clear the background of the popup
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,385 @@
+use std::sync::OnceLock;
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Deserializer};
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Built-in themes selectable in the config file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    NoColor,
+}
+
+impl std::str::FromStr for ThemeName {
+    type Err = anyhow::Error;
+
+    /// Parse the kebab-case name used in the config file
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        use serde::de::IntoDeserializer;
+        Self::deserialize(name.into_deserializer()).map_err(|err: serde::de::value::Error| {
+            anyhow::anyhow!("Invalid theme in the config file: {err}")
+        })
+    }
+}
+
+/// Styles of the TUI by their meaning
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Border of the focused element and popups
+    pub focus: Style,
+    pub unfocused: Style,
+    /// Selected line of the focused element
+    pub selection: Style,
+    pub selection_unfocused: Style,
+    /// Less important text like the topic summary
+    pub dim: Style,
+    pub good: Style,
+    /// Stale topics, disconnects and the delete popup
+    pub bad: Style,
+    /// Errors shown as a whole block
+    pub error: Style,
+    /// Topics matching an alert rule
+    pub alert: Style,
+    /// Keys in the footer
+    pub key: Style,
+    /// Labels like the search input in the footer
+    pub label: Style,
+    pub graph: Style,
+}
+
+const DARK: Theme = Theme {
+    focus: Style::new().fg(Color::LightGreen),
+    unfocused: Style::new().fg(Color::Gray),
+    selection: Style::new().fg(Color::Black).bg(Color::LightGreen),
+    selection_unfocused: Style::new().fg(Color::Black).bg(Color::Gray),
+    dim: Style::new().fg(Color::DarkGray),
+    good: Style::new().fg(Color::Green),
+    bad: Style::new().fg(Color::Red),
+    error: Style::new().fg(Color::Black).bg(Color::Red),
+    alert: Style::new()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD),
+    key: Style::new()
+        .fg(Color::Black)
+        .bg(Color::Gray)
+        .add_modifier(Modifier::BOLD),
+    label: Style::new()
+        .fg(Color::Black)
+        .bg(Color::LightGreen)
+        .add_modifier(Modifier::BOLD),
+    graph: Style::new().fg(Color::LightGreen),
+};
+
+/// Avoids telling things apart by red and green only
+const HIGH_CONTRAST: Theme = Theme {
+    focus: Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+    unfocused: Style::new().fg(Color::White),
+    selection: Style::new()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD),
+    selection_unfocused: Style::new().fg(Color::Black).bg(Color::White),
+    dim: Style::new().fg(Color::Gray),
+    good: Style::new().fg(Color::LightCyan),
+    bad: Style::new()
+        .fg(Color::LightMagenta)
+        .add_modifier(Modifier::BOLD),
+    error: Style::new()
+        .fg(Color::White)
+        .bg(Color::Magenta)
+        .add_modifier(Modifier::BOLD),
+    alert: Style::new()
+        .fg(Color::Black)
+        .bg(Color::LightCyan)
+        .add_modifier(Modifier::BOLD),
+    key: Style::new()
+        .fg(Color::Black)
+        .bg(Color::White)
+        .add_modifier(Modifier::BOLD),
+    label: Style::new()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD),
+    graph: Style::new().fg(Color::Yellow),
+};
+
+const LIGHT: Theme = Theme {
+    focus: Style::new().fg(Color::Blue),
+    unfocused: Style::new().fg(Color::DarkGray),
+    selection: Style::new().fg(Color::White).bg(Color::Blue),
+    selection_unfocused: Style::new().fg(Color::White).bg(Color::DarkGray),
+    dim: Style::new().fg(Color::Gray),
+    good: Style::new().fg(Color::Green),
+    bad: Style::new().fg(Color::Red),
+    error: Style::new().fg(Color::White).bg(Color::Red),
+    alert: Style::new()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD),
+    key: Style::new()
+        .fg(Color::White)
+        .bg(Color::DarkGray)
+        .add_modifier(Modifier::BOLD),
+    label: Style::new()
+        .fg(Color::White)
+        .bg(Color::Blue)
+        .add_modifier(Modifier::BOLD),
+    graph: Style::new().fg(Color::Blue),
+};
+
+/// Only text modifiers, see <https://no-color.org>
+const NO_COLOR: Theme = Theme {
+    focus: Style::new().add_modifier(Modifier::BOLD),
+    unfocused: Style::new(),
+    selection: Style::new().add_modifier(Modifier::REVERSED),
+    selection_unfocused: Style::new().add_modifier(Modifier::REVERSED),
+    dim: Style::new().add_modifier(Modifier::DIM),
+    good: Style::new(),
+    bad: Style::new().add_modifier(Modifier::BOLD),
+    error: Style::new().add_modifier(Modifier::REVERSED),
+    alert: Style::new().add_modifier(Modifier::REVERSED.union(Modifier::BOLD)),
+    key: Style::new().add_modifier(Modifier::REVERSED.union(Modifier::BOLD)),
+    label: Style::new().add_modifier(Modifier::REVERSED.union(Modifier::BOLD)),
+    graph: Style::new(),
+};
+
+/// `[theme]` table of the config file: a built-in theme with single styles replaced
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeOverrides {
+    #[serde(default)]
+    base: ThemeName,
+    focus: Option<StyleOverride>,
+    unfocused: Option<StyleOverride>,
+    selection: Option<StyleOverride>,
+    selection_unfocused: Option<StyleOverride>,
+    dim: Option<StyleOverride>,
+    good: Option<StyleOverride>,
+    bad: Option<StyleOverride>,
+    error: Option<StyleOverride>,
+    alert: Option<StyleOverride>,
+    key: Option<StyleOverride>,
+    label: Option<StyleOverride>,
+    graph: Option<StyleOverride>,
+}
+
+/// Replaces the given parts of a style of the base theme
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleOverride {
+    #[serde(default, deserialize_with = "deserialize_color")]
+    fg: Option<Color>,
+    #[serde(default, deserialize_with = "deserialize_color")]
+    bg: Option<Color>,
+    /// Replaces all modifiers of the base style, an empty list removes them
+    modifiers: Option<Vec<ModifierName>>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ModifierName {
+    Bold,
+    Dim,
+    Italic,
+    Underlined,
+    Reversed,
+    CrossedOut,
+}
+
+/// Color names like `light-red`, `reset`, indexed colors like `208` or hex colors like `#ff8800`
+fn deserialize_color<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Color>, D::Error> {
+    let Some(color) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    color
+        .parse()
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("invalid color {color}")))
+}
+
+impl StyleOverride {
+    fn apply(self, style: &mut Style) {
+        if let Some(fg) = self.fg {
+            style.fg = Some(fg);
+        }
+        if let Some(bg) = self.bg {
+            style.bg = Some(bg);
+        }
+        if let Some(modifiers) = self.modifiers {
+            style.add_modifier = modifiers
+                .into_iter()
+                .map(|modifier| match modifier {
+                    ModifierName::Bold => Modifier::BOLD,
+                    ModifierName::Dim => Modifier::DIM,
+                    ModifierName::Italic => Modifier::ITALIC,
+                    ModifierName::Underlined => Modifier::UNDERLINED,
+                    ModifierName::Reversed => Modifier::REVERSED,
+                    ModifierName::CrossedOut => Modifier::CROSSED_OUT,
+                })
+                .fold(Modifier::empty(), Modifier::union);
+            style.sub_modifier = Modifier::empty();
+        }
+    }
+}
+
+impl ThemeOverrides {
+    fn into_theme(self) -> Theme {
+        let mut theme = Theme::builtin(self.base).clone();
+        for (style, style_override) in [
+            (&mut theme.focus, self.focus),
+            (&mut theme.unfocused, self.unfocused),
+            (&mut theme.selection, self.selection),
+            (&mut theme.selection_unfocused, self.selection_unfocused),
+            (&mut theme.dim, self.dim),
+            (&mut theme.good, self.good),
+            (&mut theme.bad, self.bad),
+            (&mut theme.error, self.error),
+            (&mut theme.alert, self.alert),
+            (&mut theme.key, self.key),
+            (&mut theme.label, self.label),
+            (&mut theme.graph, self.graph),
+        ] {
+            if let Some(style_override) = style_override {
+                style_override.apply(style);
+            }
+        }
+        theme
+    }
+}
+
+impl Theme {
+    /// The configured theme unless the environment variable `NO_COLOR` is set.
+    ///
+    /// The config is either the name of a built-in theme or a table with a `base` theme and styles replacing its ones.
+    pub fn from_env(config: Option<&toml::Value>) -> anyhow::Result<Self> {
+        let theme = Self::from_config(config)?;
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Ok(if no_color { NO_COLOR } else { theme })
+    }
+
+    fn from_config(config: Option<&toml::Value>) -> anyhow::Result<Self> {
+        Ok(match config {
+            None => DARK,
+            Some(toml::Value::String(name)) => Self::builtin(name.parse()?).clone(),
+            Some(table) => table
+                .clone()
+                .try_into::<ThemeOverrides>()
+                .map_err(|err| anyhow::anyhow!("Invalid theme in the config file: {err}"))?
+                .into_theme(),
+        })
+    }
+
+    pub const fn builtin(name: ThemeName) -> &'static Self {
+        match name {
+            ThemeName::Dark => &DARK,
+            ThemeName::Light => &LIGHT,
+            ThemeName::HighContrast => &HIGH_CONTRAST,
+            ThemeName::NoColor => &NO_COLOR,
+        }
+    }
+
+    pub const fn border(&self, has_focus: bool) -> Style {
+        if has_focus {
+            self.focus
+        } else {
+            self.unfocused
+        }
+    }
+
+    pub const fn highlight(&self, has_focus: bool) -> Style {
+        if has_focus {
+            self.selection
+        } else {
+            self.selection_unfocused
+        }
+    }
+}
+
+/// Set the theme used for drawing. Only the first call has an effect.
+pub fn init(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// The theme set on startup, dark by default
+pub fn get() -> &'static Theme {
+    THEME.get().unwrap_or(&DARK)
+}
+
+#[test]
+fn builtin_names() {
+    let name = toml::from_str::<std::collections::BTreeMap<String, ThemeName>>(
+        "theme = \"high-contrast\"",
+    )
+    .unwrap()["theme"];
+    assert_eq!(Theme::builtin(name), &HIGH_CONTRAST);
+    assert_eq!("high-contrast".parse::<ThemeName>().unwrap(), name);
+    assert!("neon".parse::<ThemeName>().is_err());
+}
+
+#[test]
+fn overrides_replace_single_styles() {
+    let config = toml::from_str::<toml::Table>(
+        r##"
+[theme]
+base = "light"
+good = { fg = "light-cyan" }
+bad = { fg = "#ff8800", modifiers = ["bold", "underlined"] }
+alert = { modifiers = [] }
+"##,
+    )
+    .unwrap();
+    let theme = Theme::from_config(config.get("theme")).unwrap();
+    assert_eq!(theme.focus, LIGHT.focus);
+    assert_eq!(theme.good, Style::new().fg(Color::LightCyan));
+    assert_eq!(
+        theme.bad,
+        Style::new()
+            .fg(Color::Rgb(0xff, 0x88, 0x00))
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    );
+    assert_eq!(theme.alert, Style::new().fg(Color::Black).bg(Color::Yellow));
+}
+
+#[test]
+fn invalid_overrides_fail() {
+    for theme in [
+        "theme = { good = { fg = \"not-a-color\" } }",
+        "theme = { base = \"neon\" }",
+        "theme = { bold = { fg = \"red\" } }",
+        "theme = { good = { modifiers = [\"blink-fast\"] } }",
+    ] {
+        let config = toml::from_str::<toml::Table>(theme).unwrap();
+        assert!(Theme::from_config(config.get("theme")).is_err(), "{theme}");
+    }
+}
+
+#[test]
+fn no_color_has_no_colors() {
+    let theme = NO_COLOR;
+    for style in [
+        theme.focus,
+        theme.unfocused,
+        theme.selection,
+        theme.selection_unfocused,
+        theme.dim,
+        theme.good,
+        theme.bad,
+        theme.error,
+        theme.alert,
+        theme.key,
+        theme.label,
+        theme.graph,
+    ] {
+        assert_eq!(style.fg, None);
+        assert_eq!(style.bg, None);
+    }
+}
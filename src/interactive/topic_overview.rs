@@ -1,16 +1,18 @@
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Style};
 use ratatui::widgets::{Block, BorderType, Scrollbar, ScrollbarOrientation};
 use ratatui::Frame;
 use tui_tree_widget::{Tree, TreeState};
 
-use super::zenoh_history::ZenohHistory;
-use super::ui::{focus_color, BORDERS_TOP_RIGHT};
+use super::zenoh_history::{TreeItems, ZenohHistory};
+use super::theme;
+use super::ui::BORDERS_TOP_RIGHT;
 
 #[derive(Default)]
 pub struct TopicOverview {
     pub last_area: Rect,
     pub search: String,
+    /// Only show stale topics and their parents
+    pub stale_only: bool,
     pub state: TreeState<String>,
 }
 
@@ -24,9 +26,20 @@ impl TopicOverview {
     }
 
     pub fn draw(&mut self, frame: &mut Frame, area: Rect, history: &ZenohHistory, has_focus: bool) {
-        let (topic_amount, message_amount, tree_items) = history.to_tree_items();
-        let title = format!("Topics ({topic_amount}, {message_amount} messages)");
-        let focus_color = focus_color(has_focus);
+        let TreeItems {
+            topics,
+            messages,
+            stale,
+            items: tree_items,
+        } = history.to_tree_items(self.stale_only);
+        let title = if self.stale_only {
+            format!("Stale Topics ({stale} of {topics}, {messages} messages)")
+        } else if stale > 0 {
+            format!("Topics ({topics}, {messages} messages, {stale} stale)")
+        } else {
+            format!("Topics ({topics}, {messages} messages)")
+        };
+        let theme = theme::get();
         let widget = Tree::new(&tree_items)
             .unwrap()
             .experimental_scrollbar(Some(
@@ -35,12 +48,12 @@ impl TopicOverview {
                     .end_symbol(None)
                     .track_symbol(None),
             ))
-            .highlight_style(Style::new().fg(Color::Black).bg(focus_color))
+            .highlight_style(theme.highlight(has_focus))
             .block(
                 Block::new()
                     .border_type(BorderType::Rounded)
                     .borders(BORDERS_TOP_RIGHT)
-                    .border_style(Style::new().fg(focus_color))
+                    .border_style(theme.border(has_focus))
                     .title_alignment(Alignment::Center)
                     .title(title),
             );
@@ -1,5 +1,5 @@
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::widgets::Borders;
 
 pub const BORDERS_TOP_RIGHT: Borders = Borders::TOP.union(Borders::RIGHT);
@@ -9,16 +9,13 @@ pub enum ElementInFocus {
     TopicOverview,
     TopicSearch,
     Payload,
+    PayloadPath,
     HistoryTable,
     CleanPopup(String),
-}
-
-pub const fn focus_color(has_focus: bool) -> Color {
-    if has_focus {
-        Color::LightGreen
-    } else {
-        Color::Gray
-    }
+    StatisticsPopup(String),
+    SessionPopup,
+    AdminView,
+    ConnectionPopup,
 }
 
 pub const fn split_area_vertically(area: Rect, height_first: u16) -> (Rect, Rect) {
@@ -34,6 +31,26 @@ pub const fn split_area_vertically(area: Rect, height_first: u16) -> (Rect, Rect
     (first, second)
 }
 
+/// Centered area of the given size clamped to fit into the `area`
+pub fn popup_area(area: Rect, width: usize, height: usize) -> Rect {
+    #[allow(clippy::cast_possible_truncation)]
+    let width = width.min(area.width.saturating_sub(4) as usize) as u16;
+    #[allow(clippy::cast_possible_truncation)]
+    let height = height.min(area.height.saturating_sub(2) as usize) as u16;
+    Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Highest scroll position of a bordered popup which still shows its last line at the bottom
+pub fn max_scroll(text_height: usize, area: Rect) -> u16 {
+    let visible = usize::from(area.height.saturating_sub(2));
+    u16::try_from(text_height.saturating_sub(visible)).unwrap_or(u16::MAX)
+}
+
 #[test]
 pub fn split_vertically_example() {
     let area = Rect::new(5, 10, 10, 14);
@@ -41,3 +58,22 @@ pub fn split_vertically_example() {
     assert_eq!(first, Rect::new(5, 10, 10, 7));
     assert_eq!(second, Rect::new(5, 17, 10, 7));
 }
+
+#[test]
+fn popup_area_is_centered() {
+    let area = Rect::new(0, 0, 100, 50);
+    assert_eq!(popup_area(area, 20, 10), Rect::new(40, 20, 20, 10));
+}
+
+#[test]
+fn popup_area_is_clamped() {
+    let area = Rect::new(0, 0, 30, 10);
+    assert_eq!(popup_area(area, 100, 100), Rect::new(2, 1, 26, 8));
+}
+
+#[test]
+fn max_scroll_keeps_last_line_visible() {
+    let area = Rect::new(0, 0, 30, 12);
+    assert_eq!(max_scroll(25, area), 15);
+    assert_eq!(max_scroll(5, area), 0);
+}
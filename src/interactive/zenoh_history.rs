@@ -1,25 +1,49 @@
 use std::collections::HashMap;
+use std::time::Instant;
 
 use ego_tree::{NodeId, NodeRef, Tree};
-use ratatui::style::{Color, Style};
+use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use tui_tree_widget::TreeItem;
+use zenoh::bytes::Encoding;
+use zenoh::sample::SampleKind;
+
+use crate::interactive::theme;
 use crate::interactive::ui::STYLE_BOLD;
+use crate::staleness::{Period, StaleSettings};
+use crate::statistics::Observation;
 use crate::zenoh_client::HistoryEntry;
 
-pub const STYLE_DARKGRAY: Style = Style::new().fg(Color::DarkGray);
+/// Payloads up to this size or the payload size limit are kept as [`LatestPayload`].
+/// Every topic keeps one untruncated payload in memory, so larger ones are not kept.
+pub const LATEST_PAYLOAD_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// Untruncated payload of a put which can be published again
+#[derive(Clone)]
+pub struct LatestPayload {
+    pub payload: Vec<u8>,
+    pub encoding: Encoding,
+}
 
 struct Topic {
     /// Key expression `foo/bar` would have the leaf `bar`
     leaf: Box<str>,
     history: Vec<HistoryEntry>,
+    /// `None` when the last sample was a delete or above the [`LATEST_PAYLOAD_SIZE_LIMIT`]
+    latest: Option<LatestPayload>,
+    period: Period,
+    /// An alert rule with the highlight action matches
+    highlighted: bool,
 }
 
 impl Topic {
-    const fn new(leaf: Box<str>) -> Self {
+    fn new(leaf: Box<str>) -> Self {
         Self {
             leaf,
             history: Vec::new(),
+            latest: None,
+            period: Period::default(),
+            highlighted: false,
         }
     }
 }
@@ -28,19 +52,34 @@ struct RecursiveTreeItemGenerator {
     messages_below: usize,
     messages: usize,
     topics_below: usize,
-    tree_item: TreeItem<'static, String>,
+    stale: bool,
+    stale_below: usize,
+    /// `None` when filtered out
+    tree_item: Option<TreeItem<'static, String>>,
+}
+
+pub struct TreeItems {
+    pub topics: usize,
+    pub messages: usize,
+    pub stale: usize,
+    pub items: Vec<TreeItem<'static, String>>,
 }
 
 pub struct ZenohHistory {
     tree: Tree<Topic>,
     ids: HashMap<String, NodeId>,
+    stale_settings: StaleSettings,
+    /// Maximum amount of history entries kept per topic
+    retention: Option<usize>,
 }
 
 impl ZenohHistory {
-    pub fn new() -> Self {
+    pub fn new(stale_settings: StaleSettings, retention: Option<usize>) -> Self {
         Self {
             tree: Tree::new(Topic::new("".into())),
             ids: HashMap::new(),
+            stale_settings,
+            retention,
         }
     }
 
@@ -72,12 +111,50 @@ impl ZenohHistory {
 
     pub fn add(&mut self, keyexpr: String, history_entry: HistoryEntry) {
         let id = self.entry(keyexpr);
-        self.tree
-            .get_mut(id)
-            .unwrap()
-            .value()
-            .history
-            .push(history_entry);
+        let mut node = self.tree.get_mut(id).unwrap();
+        let topic = node.value();
+        if history_entry.kind == SampleKind::Delete {
+            topic.period.forget();
+        } else {
+            topic.period.observe(Instant::now());
+        }
+        topic.history.push(history_entry);
+        if let Some(retention) = self.retention {
+            let excess = topic.history.len().saturating_sub(retention.max(1));
+            topic.history.drain(..excess);
+        }
+    }
+
+    pub fn set_highlighted(&mut self, keyexpr: &str, highlighted: bool) {
+        if let Some(mut node) = self.ids.get(keyexpr).and_then(|id| self.tree.get_mut(*id)) {
+            node.value().highlighted = highlighted;
+        }
+    }
+
+    pub fn set_latest(&mut self, keyexpr: &str, latest: Option<LatestPayload>) {
+        if let Some(mut node) = self.ids.get(keyexpr).and_then(|id| self.tree.get_mut(*id)) {
+            node.value().latest = latest;
+        }
+    }
+
+    pub fn get_latest(&self, keyexpr: &str) -> Option<&LatestPayload> {
+        let id = self.ids.get(keyexpr)?;
+        self.tree.get(*id)?.value().latest.as_ref()
+    }
+
+    /// Latest payloads of the topic and all topics below which currently have a value
+    pub fn get_latest_below(&self, base: &str) -> Vec<(String, LatestPayload)> {
+        let mut latest = self
+            .ids
+            .iter()
+            .filter(|(key, _)| is_topic_below(base, key))
+            .filter_map(|(key, id)| {
+                let latest = self.tree.get(*id)?.value().latest.clone()?;
+                Some((key.clone(), latest))
+            })
+            .collect::<Vec<_>>();
+        latest.sort_by(|(a, _), (b, _)| a.cmp(b));
+        latest
     }
 
     pub fn get(&self, keyexpr: &str) -> Option<&Vec<HistoryEntry>> {
@@ -111,16 +188,44 @@ impl ZenohHistory {
             .collect()
     }
 
-    /// Returns (`topic_amount`, `message_amount`, `TreeItem`s)
-    pub fn to_tree_items(&self) -> (usize, usize, Vec<TreeItem<'static, String>>) {
-        fn build_recursive(prefix: &[&str], node: NodeRef<Topic>) -> RecursiveTreeItemGenerator {
-            let Topic { leaf, history } = node.value();
+    /// Metadata of all entries of the topic and all topics below
+    pub fn get_observations_below(&self, base: &str) -> Vec<Observation> {
+        self.ids
+            .iter()
+            .filter(|(key, _)| is_topic_below(base, key))
+            .filter_map(|(_, id)| self.tree.get(*id))
+            .flat_map(|node| &node.value().history)
+            .map(|entry| Observation {
+                time: entry.time.as_optional().copied(),
+                size: entry.payload_size,
+            })
+            .collect()
+    }
+
+    /// Tree of all topics. With `stale_only` only stale topics and their parents are included.
+    pub fn to_tree_items(&self, stale_only: bool) -> TreeItems {
+        fn build_recursive(
+            prefix: &[&str],
+            node: NodeRef<Topic>,
+            now: Instant,
+            stale_settings: &StaleSettings,
+            stale_only: bool,
+        ) -> RecursiveTreeItemGenerator {
+            let Topic {
+                leaf,
+                history,
+                period,
+                highlighted,
+            } = node.value();
             let mut topic = prefix.to_vec();
             topic.push(leaf);
 
-            let entries_below = node.children().map(|node| build_recursive(&topic, node));
+            let entries_below = node
+                .children()
+                .map(|node| build_recursive(&topic, node, now, stale_settings, stale_only));
             let mut messages_below: usize = 0;
             let mut topics_below: usize = 0;
+            let mut stale_below: usize = 0;
             let mut children = Vec::new();
             for below in entries_below {
                 messages_below = messages_below
@@ -129,34 +234,66 @@ impl ZenohHistory {
                 topics_below = topics_below
                     .saturating_add(usize::from(below.messages > 0))
                     .saturating_add(below.topics_below);
-                children.push(below.tree_item);
+                stale_below = stale_below
+                    .saturating_add(usize::from(below.stale))
+                    .saturating_add(below.stale_below);
+                children.extend(below.tree_item);
             }
 
+            let stale = period.is_stale(now, stale_settings);
             let meta = history.last().map(|entry| &entry.payload).map_or_else(
-                || format!("({topics_below} topics, {messages_below} messages)"),
+                || {
+                    if stale_below > 0 {
+                        format!("({topics_below} topics, {messages_below} messages, {stale_below} stale)")
+                    } else {
+                        format!("({topics_below} topics, {messages_below} messages)")
+                    }
+                },
                 |payload| format!("= {payload}"),
             );
-            let text = Line::from(vec![
-                Span::styled(leaf.to_string(), STYLE_BOLD),
+            let theme = theme::get();
+            let stale_style = theme.bad.add_modifier(Modifier::BOLD);
+            let leaf_style = if *highlighted {
+                theme.alert
+            } else if stale {
+                stale_style
+            } else {
+                STYLE_BOLD
+            };
+            let mut text = vec![
+                Span::styled(leaf.to_string(), leaf_style),
                 Span::raw(" "),
-                Span::styled(meta, STYLE_DARKGRAY),
-            ]);
+                Span::styled(meta, theme.dim),
+            ];
+            if stale {
+                if let Some(last_seen) = period.last_seen() {
+                    let ago = now.saturating_duration_since(last_seen).as_secs();
+                    text.push(Span::styled(format!(" stale for {ago}s"), stale_style));
+                }
+            }
+
+            let tree_item = (!stale_only || stale || stale_below > 0)
+                .then(|| TreeItem::new(leaf.to_string(), Line::from(text), children).unwrap());
 
             RecursiveTreeItemGenerator {
                 messages_below,
                 messages: history.len(),
                 topics_below,
-                tree_item: TreeItem::new(leaf.to_string(), text, children).unwrap(),
+                stale,
+                stale_below,
+                tree_item,
             }
         }
 
+        let now = Instant::now();
         let children = self
             .tree
             .root()
             .children()
-            .map(|node| build_recursive(&[], node));
+            .map(|node| build_recursive(&[], node, now, &self.stale_settings, stale_only));
         let mut topics: usize = 0;
         let mut messages: usize = 0;
+        let mut stale: usize = 0;
         let mut items = Vec::new();
         for child in children {
             topics = topics
@@ -165,15 +302,21 @@ impl ZenohHistory {
             messages = messages
                 .saturating_add(child.messages)
                 .saturating_add(child.messages_below);
-            items.push(child.tree_item);
+            stale = stale
+                .saturating_add(usize::from(child.stale))
+                .saturating_add(child.stale_below);
+            items.extend(child.tree_item);
+        }
+        TreeItems {
+            topics,
+            messages,
+            stale,
+            items,
         }
-        (topics, messages, items)
     }
 
     #[cfg(test)]
     pub fn example() -> Self {
-        use zenoh::sample::SampleKind;
-
         fn entry(payload: &str) -> HistoryEntry {
             HistoryEntry {
                 kind: SampleKind::Put,
@@ -183,7 +326,7 @@ impl ZenohHistory {
             }
         }
 
-        let mut history = Self::new();
+        let mut history = Self::new(StaleSettings::default(), None);
         history.add("test".to_owned(), entry("A"));
         history.add("foo/test".to_owned(), entry("B"));
         history.add("test".to_owned(), entry("C"));
@@ -218,15 +361,80 @@ fn topics_below_finds_itself_works() {
     assert_eq!(actual, ["test"]);
 }
 
+#[test]
+fn latest_below_skips_deleted() {
+    let mut example = ZenohHistory::example();
+    let latest = |payload: &str| {
+        Some(LatestPayload {
+            payload: payload.into(),
+            encoding: Encoding::TEXT_PLAIN,
+        })
+    };
+    example.set_latest("foo/test", latest("B"));
+    example.set_latest("foo/bar", latest("D"));
+    example.set_latest("test", latest("C"));
+    example.set_latest("foo/bar", None);
+    let actual = example.get_latest_below("foo");
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].0, "foo/test");
+    assert_eq!(actual[0].1.payload, b"B");
+    assert_eq!(actual[0].1.encoding, Encoding::TEXT_PLAIN);
+}
+
+#[test]
+fn observations_below_works() {
+    let example = ZenohHistory::example();
+    assert_eq!(example.get_observations_below("foo").len(), 2);
+    assert_eq!(example.get_observations_below("test").len(), 2);
+    assert_eq!(example.get_observations_below("nope").len(), 0);
+}
+
 #[test]
 fn tree_items_works() {
     let example = ZenohHistory::example();
-    let (topics, messages, items) = example.to_tree_items();
+    let TreeItems {
+        topics,
+        messages,
+        stale,
+        items,
+    } = example.to_tree_items(false);
     assert_eq!(topics, 4);
     assert_eq!(messages, 5);
+    assert_eq!(stale, 0);
     dbg!(&items);
     assert_eq!(items.len(), 3);
     assert_eq!(items[0].children().len(), 2);
     assert_eq!(items[1].children().len(), 0);
     assert_eq!(items[2].children().len(), 1);
 }
+
+#[test]
+fn retention_drops_oldest() {
+    let mut history = ZenohHistory::new(StaleSettings::default(), Some(2));
+    for payload in ["A", "B", "C"] {
+        history.add(
+            "test".to_owned(),
+            HistoryEntry {
+                kind: SampleKind::Put,
+                time: crate::zenoh_client::Time::new_now(),
+                payload_size: payload.len(),
+                payload: crate::payload::Payload::unlimited(payload.into()),
+            },
+        );
+    }
+    let payloads = history
+        .get("test")
+        .unwrap()
+        .iter()
+        .map(|entry| entry.payload.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(payloads, ["B", "C"]);
+}
+
+#[test]
+fn tree_items_stale_only_hides_fresh() {
+    let example = ZenohHistory::example();
+    let tree_items = example.to_tree_items(true);
+    assert_eq!(tree_items.topics, 4);
+    assert!(tree_items.items.is_empty());
+}
@@ -1,21 +1,31 @@
 use std::sync::{mpsc, Arc, RwLock, RwLockReadGuard};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use zenoh::handlers::fifo::FifoChannel;
 use zenoh::sample::SampleKind;
 use zenoh::{Session, Wait};
 
-use crate::interactive::zenoh_history::ZenohHistory;
+use crate::interactive::zenoh_history::{LatestPayload, ZenohHistory, LATEST_PAYLOAD_SIZE_LIMIT};
 use crate::payload::Payload;
-use crate::zenoh_client::{HistoryEntry, Time};
+use crate::rules::{Alert, Rules};
+use crate::staleness::StaleSettings;
+use crate::zenoh_client::{ConnectionMonitor, HistoryEntry, SessionStatus, Time};
 
-type ConnectionErrorArc = Arc<RwLock<Option<String>>>;
+type AlertsArc = Arc<RwLock<Vec<Alert>>>;
+type ConnectionMonitorArc = Arc<RwLock<ConnectionMonitor>>;
 type HistoryArc = Arc<RwLock<ZenohHistory>>;
+type SessionStatusArc = Arc<RwLock<SessionStatus>>;
+
+/// How often the connected routers and peers are queried
+const SESSION_STATUS_INTERVAL: Duration = Duration::from_secs(2);
 
 pub struct ZenohThread {
     session: Arc<Session>,
-    connection_err: ConnectionErrorArc,
+    alerts: AlertsArc,
+    connection_monitor: ConnectionMonitorArc,
     history: HistoryArc,
+    session_status: SessionStatusArc,
 }
 
 impl ZenohThread {
@@ -23,16 +33,43 @@ impl ZenohThread {
         session: Arc<Session>,
         subscribe_keyexpr: Vec<String>,
         payload_size_limit: usize,
+        retention: Option<usize>,
+        stale_settings: StaleSettings,
+        mut rules: Option<Rules>,
     ) -> anyhow::Result<Self> {
-        let connection_err = Arc::new(RwLock::new(None));
-        let history = Arc::new(RwLock::new(ZenohHistory::new()));
+        let alerts = Arc::new(RwLock::new(Vec::new()));
+        let history = Arc::new(RwLock::new(ZenohHistory::new(stale_settings, retention)));
+        let status = SessionStatus::query(&session);
+        let connection_monitor =
+            Arc::new(RwLock::new(ConnectionMonitor::new(&status, Instant::now())));
+        let session_status = Arc::new(RwLock::new(status));
         let (tx, rx) = mpsc::channel();
 
+        {
+            let session = Arc::clone(&session);
+            let session_status = Arc::clone(&session_status);
+            let connection_monitor = Arc::clone(&connection_monitor);
+            thread::Builder::new()
+                .name("zenoh session status".to_owned())
+                .spawn(move || loop {
+                    thread::sleep(SESSION_STATUS_INTERVAL);
+                    let status = SessionStatus::query(&session);
+                    connection_monitor
+                        .write()
+                        .expect("connection monitor reader panicked")
+                        .update(&status, Instant::now());
+                    *session_status
+                        .write()
+                        .expect("session status reader panicked") = status;
+                })
+                .expect("should be able to spawn a thread");
+        }
+
         for keyexpr in &subscribe_keyexpr {
             let session = Arc::clone(&session);
             let keyexpr = keyexpr.clone();
             let tx = tx.clone();
-            let connection_err = Arc::clone(&connection_err);
+            let connection_monitor = Arc::clone(&connection_monitor);
             thread::Builder::new()
                 .name(format!("zenoh subscriber {keyexpr}"))
                 .spawn(move || {
@@ -43,7 +80,10 @@ impl ZenohThread {
                     {
                         Ok(subscriber) => subscriber,
                         Err(err) => {
-                            *connection_err.write().unwrap() = Some(err.to_string());
+                            connection_monitor
+                                .write()
+                                .unwrap()
+                                .record_error(err.to_string());
                             return;
                         }
                     };
@@ -55,7 +95,10 @@ impl ZenohThread {
                                 }
                             }
                             Err(err) => {
-                                *connection_err.write().unwrap() = Some(err.to_string());
+                                connection_monitor
+                                    .write()
+                                    .unwrap()
+                                    .record_error(err.to_string());
                                 break;
                             }
                         }
@@ -67,21 +110,49 @@ impl ZenohThread {
         drop(tx);
 
         {
+            let alerts = Arc::clone(&alerts);
             let history = Arc::clone(&history);
-            let connection_err = Arc::clone(&connection_err);
+            let connection_monitor = Arc::clone(&connection_monitor);
             thread::Builder::new()
                 .name("zenoh history".to_owned())
                 .spawn(move || {
                     for sample in rx {
-                        *connection_err.write().unwrap() = None;
+                        connection_monitor.write().unwrap().clear_error();
+                        let keyexpr = sample.key_expr().as_str().to_owned();
                         let payload = sample.payload().to_bytes().to_vec();
                         let time = if sample.kind() == SampleKind::Delete {
                             Time::Unknown
                         } else {
                             Time::new_now()
                         };
-                        history.write().unwrap().add(
-                            sample.key_expr().as_str().to_owned(),
+
+                        let matching_rules = rules
+                            .as_mut()
+                            .filter(|rules| rules.applies_to(sample.key_expr()));
+                        let highlighted = matching_rules.is_some_and(|rules| {
+                            // Evaluate the full payload as the truncated one might not be parseable
+                            let new_alerts = rules
+                                .evaluate(sample.key_expr(), &Payload::unlimited(payload.clone()));
+                            let mut alerts = alerts.write().unwrap();
+                            alerts.retain(|alert| rules.is_active(alert));
+                            alerts.extend(
+                                new_alerts
+                                    .into_iter()
+                                    .filter(|alert| alert.banner || alert.action_error.is_some()),
+                            );
+                            rules.is_highlighted(&keyexpr)
+                        });
+
+                        let keep_latest = sample.kind() == SampleKind::Put
+                            && payload.len() <= LATEST_PAYLOAD_SIZE_LIMIT.max(payload_size_limit);
+                        let latest = keep_latest.then(|| LatestPayload {
+                            payload: payload.clone(),
+                            encoding: sample.encoding().clone(),
+                        });
+
+                        let mut history = history.write().unwrap();
+                        history.add(
+                            keyexpr.clone(),
                             HistoryEntry {
                                 kind: sample.kind(),
                                 time,
@@ -89,6 +160,8 @@ impl ZenohThread {
                                 payload: Payload::truncated(payload, payload_size_limit),
                             },
                         );
+                        history.set_highlighted(&keyexpr, highlighted);
+                        history.set_latest(&keyexpr, latest);
                     }
                 })
                 .expect("should be able to spawn a thread");
@@ -96,17 +169,44 @@ impl ZenohThread {
 
         Ok(Self {
             session,
-            connection_err,
+            alerts,
+            connection_monitor,
             history,
+            session_status,
         })
     }
 
+    pub fn session(&self) -> Arc<Session> {
+        Arc::clone(&self.session)
+    }
+
     pub fn has_connection_err(&self) -> Option<String> {
-        self.connection_err
+        self.get_connection_monitor()
+            .current_error()
+            .map(ToString::to_string)
+    }
+
+    /// Alerts of rules with the banner action which still match
+    pub fn get_alerts(&self) -> Vec<String> {
+        self.alerts
             .read()
             .expect("zenoh history thread panicked")
-            .as_ref()
-            .map(ToString::to_string)
+            .iter()
+            .map(|alert| format!("{} {alert}", alert.time))
+            .collect()
+    }
+
+    pub fn get_session_status(&self) -> SessionStatus {
+        self.session_status
+            .read()
+            .expect("zenoh session status thread panicked")
+            .clone()
+    }
+
+    pub fn get_connection_monitor(&self) -> RwLockReadGuard<'_, ConnectionMonitor> {
+        self.connection_monitor
+            .read()
+            .expect("zenoh session status thread panicked")
     }
 
     pub fn get_history(&self) -> RwLockReadGuard<'_, ZenohHistory> {
@@ -123,6 +223,31 @@ impl ZenohThread {
             .uncache_topic_entry(keyexpr, index)
     }
 
+    /// Latest payloads of the keys affected by [`Self::clean_below`] to undo it later
+    pub fn snapshot_below(&self, keyexpr: &str) -> Vec<(String, LatestPayload)> {
+        self.get_history().get_latest_below(keyexpr)
+    }
+
+    /// Show the error in the connection events
+    pub fn record_error(&self, error: String) {
+        self.connection_monitor
+            .write()
+            .expect("connection monitor reader panicked")
+            .record_error(error);
+    }
+
+    /// Publish the payloads of a snapshot again
+    pub fn restore(&self, snapshot: &[(String, LatestPayload)]) -> anyhow::Result<()> {
+        for (keyexpr, latest) in snapshot {
+            self.session
+                .put(keyexpr, latest.payload.clone())
+                .encoding(latest.encoding.clone())
+                .wait()
+                .map_err(|err| anyhow::anyhow!(err))?;
+        }
+        Ok(())
+    }
+
     /// Clean on Zenoh
     pub fn clean_below(&self, keyexpr: &str) -> anyhow::Result<()> {
         let keyexprs = self.get_history().get_topics_below(keyexpr);
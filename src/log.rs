@@ -1,64 +1,43 @@
-use std::sync::{mpsc, Arc};
-use std::thread;
+use std::sync::Arc;
+use std::time::Instant;
 
-use zenoh::handlers::fifo::FifoChannel;
 use zenoh::sample::SampleKind;
-use zenoh::{Session, Wait};
-
-use serde::Serialize;
+use zenoh::Session;
 
+use crate::cli::TimeFormat;
 use crate::format;
-use crate::payload::Payload;
-use crate::zenoh_client::Time;
+use crate::log_file::LogFiles;
+use crate::log_filter::LogFilter;
+use crate::log_format::{LogFormat, Record};
+use crate::payload::{JsonSelector, Payload};
+use crate::rules::Rules;
+use crate::zenoh_client::{self, Time};
 
-#[derive(Serialize)]
-struct JsonLog {
-    time: Time,
-    kind: &'static str,
-    keyexpr: String,
-    size: usize,
-    payload: Payload,
+/// How the logged samples are printed
+pub struct Output {
+    pub format: LogFormat,
+    pub time_format: TimeFormat,
+    /// Only log the value at this path and skip samples without it
+    pub select: Option<Vec<JsonSelector>>,
+    /// Write into files instead of stdout
+    pub files: Option<LogFiles>,
 }
 
-pub fn show(session: Arc<Session>, keyexprs: Vec<String>, json: bool) -> anyhow::Result<()> {
-    let (tx, rx) = mpsc::channel();
+pub fn show(
+    session: &Arc<Session>,
+    keyexprs: Vec<String>,
+    mut output: Output,
+    mut filter: LogFilter,
+    mut rules: Option<Rules>,
+) -> anyhow::Result<()> {
+    let rx = zenoh_client::subscribe_all(session, keyexprs);
 
-    for keyexpr in keyexprs {
-        let session = Arc::clone(&session);
-        let tx = tx.clone();
-        thread::Builder::new()
-            .name(format!("zenoh log {keyexpr}"))
-            .spawn(move || {
-                let subscriber = match session
-                    .declare_subscriber(&keyexpr)
-                    .with(FifoChannel::default())
-                    .wait()
-                {
-                    Ok(subscriber) => subscriber,
-                    Err(err) => {
-                        eprintln!("Failed to subscribe to {keyexpr}: {err}");
-                        return;
-                    }
-                };
-                loop {
-                    match subscriber.recv() {
-                        Ok(sample) => {
-                            if tx.send(sample).is_err() {
-                                break;
-                            }
-                        }
-                        Err(err) => {
-                            eprintln!("Subscriber error: {err}");
-                            break;
-                        }
-                    }
-                }
-            })
-            .expect("should be able to spawn subscriber thread");
+    if output.files.is_none() {
+        if let Some(header) = output.format.header() {
+            println!("{header}");
+        }
     }
 
-    drop(tx);
-
     for sample in rx {
         let kind = format::kind(sample.kind());
         let time = if sample.kind() == SampleKind::Delete {
@@ -68,23 +47,59 @@ pub fn show(session: Arc<Session>, keyexprs: Vec<String>, json: bool) -> anyhow:
         };
         let keyexpr = sample.key_expr().as_str().to_owned();
         let payload = sample.payload().to_bytes().to_vec();
+        // Stays the size of the received payload even with --select
         let size = payload.len();
         let payload = Payload::unlimited(payload);
 
-        if json {
-            let json = serde_json::to_string(&JsonLog {
-                time,
-                kind,
-                keyexpr,
-                size,
-                payload,
-            })
-            .expect("Should be able to format log line as JSON");
-            println!("{json}");
+        if let Some(rules) = &mut rules {
+            for alert in rules.evaluate(sample.key_expr(), &payload) {
+                eprintln!("{:12} ALERT {alert}", alert.time);
+            }
+        }
+
+        let Some(repeats) =
+            filter.matches(sample.key_expr(), sample.kind(), &payload, Instant::now())
+        else {
+            continue;
+        };
+        if repeats > 0 {
+            report_repeats(&keyexpr, repeats);
+        }
+
+        let payload = match &output.select {
+            Some(path) => match payload.select(path) {
+                Some(value) => Payload::selected(value),
+                None => continue,
+            },
+            None => payload,
+        };
+
+        let record = Record {
+            time,
+            kind,
+            keyexpr: &keyexpr,
+            size,
+            payload: &payload,
+        };
+        let line = output.format.format(&record, output.time_format);
+        if let Some(files) = &mut output.files {
+            files.write_line(&keyexpr, &line)?;
         } else {
-            println!("{time:12} Kind:{kind:6} {keyexpr:50} Payload({size:>3}): {payload}");
+            println!("{line}");
         }
     }
 
+    // Repeats are otherwise only reported once the key changes again
+    for (keyexpr, repeats) in filter.take_pending_repeats() {
+        report_repeats(&keyexpr, repeats);
+    }
+
     Ok(())
 }
+
+fn report_repeats(keyexpr: &str, repeats: usize) {
+    eprintln!(
+        "{:12} REPEATED {keyexpr} previous payload {repeats} times",
+        Time::new_now()
+    );
+}
@@ -0,0 +1,306 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::cli::LogFileOptions;
+
+/// Log files which are rotated by size or age
+pub struct LogFiles {
+    path: PathBuf,
+    max_size: Option<u64>,
+    max_age: Option<Duration>,
+    gzip: bool,
+    split_by_key: bool,
+    /// Written at the start of each file
+    header: Option<&'static str>,
+    /// Open files per top-level key or the empty string without `--split-by-key`
+    open: HashMap<String, OpenFile>,
+    /// Running `--gzip` of rotated files, joined on drop to not leave them uncompressed
+    compressing: Vec<JoinHandle<()>>,
+}
+
+struct OpenFile {
+    path: PathBuf,
+    writer: LineWriter<File>,
+    size: u64,
+    opened: Instant,
+}
+
+impl LogFiles {
+    /// `None` when the log should be printed to stdout
+    pub fn new(options: LogFileOptions, header: Option<&'static str>) -> Option<Self> {
+        Some(Self {
+            path: options.output?,
+            max_size: options.rotate_size,
+            max_age: options.rotate_every,
+            gzip: options.gzip,
+            split_by_key: options.split_by_key,
+            header,
+            open: HashMap::new(),
+            compressing: Vec::new(),
+        })
+    }
+
+    pub fn write_line(&mut self, keyexpr: &str, line: &str) -> anyhow::Result<()> {
+        let group = if self.split_by_key {
+            keyexpr.split('/').next().unwrap_or_default()
+        } else {
+            ""
+        };
+        let file = match self.open.entry(group.to_owned()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(OpenFile::open(group_path(&self.path, group), self.header)?)
+            }
+        };
+
+        let line_size = line.len() as u64 + 1;
+        let too_big = self
+            .max_size
+            .is_some_and(|max_size| file.size > 0 && file.size + line_size > max_size);
+        let too_old = self
+            .max_age
+            .is_some_and(|max_age| file.opened.elapsed() >= max_age);
+        if too_big || too_old {
+            if let Some(compressing) = file.rotate(self.header, self.gzip)? {
+                self.compressing.retain(|handle| !handle.is_finished());
+                self.compressing.push(compressing);
+            }
+        }
+
+        writeln!(file.writer, "{line}")
+            .map_err(|err| anyhow::anyhow!("Failed to write to {}: {err}", file.path.display()))?;
+        file.size += line_size;
+        Ok(())
+    }
+}
+
+impl Drop for LogFiles {
+    fn drop(&mut self) {
+        for handle in self.compressing.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl OpenFile {
+    fn open(path: PathBuf, header: Option<&str>) -> anyhow::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| anyhow::anyhow!("Failed to open {}: {err}", path.display()))?;
+        let mut size = file.metadata()?.len();
+        let mut writer = LineWriter::new(file);
+        if let (0, Some(header)) = (size, header) {
+            writeln!(writer, "{header}")?;
+            size = header.len() as u64 + 1;
+        }
+        Ok(Self {
+            path,
+            writer,
+            size,
+            opened: Instant::now(),
+        })
+    }
+
+    /// Rename the current file and continue with a new one at the same path.
+    ///
+    /// Returns the thread compressing the rotated file with `gzip`.
+    fn rotate(
+        &mut self,
+        header: Option<&str>,
+        gzip: bool,
+    ) -> anyhow::Result<Option<JoinHandle<()>>> {
+        self.writer.flush()?;
+        let rotated = unused_rotated_path(&self.path, &Local::now());
+        fs::rename(&self.path, &rotated)
+            .map_err(|err| anyhow::anyhow!("Failed to rotate {}: {err}", self.path.display()))?;
+        *self = Self::open(self.path.clone(), header)?;
+        if !gzip {
+            return Ok(None);
+        }
+        // Compressing big files takes a while, keep logging meanwhile
+        let handle = thread::Builder::new()
+            .name(format!("gzip {}", rotated.display()))
+            .spawn(move || {
+                if let Err(err) = compress(&rotated) {
+                    eprintln!("Failed to gzip {}: {err}", rotated.display());
+                }
+            })
+            .expect("should be able to spawn gzip thread");
+        Ok(Some(handle))
+    }
+}
+
+/// Path of the file for a top-level key like `demo.sensors.log` for `demo.log`
+fn group_path(path: &Path, group: &str) -> PathBuf {
+    if group.is_empty() {
+        return path.to_owned();
+    }
+    let group = group
+        .chars()
+        .map(|char| {
+            if char.is_alphanumeric() || matches!(char, '-' | '_') {
+                char
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{stem}.{group}.{}", extension.to_string_lossy()),
+        None => format!("{stem}.{group}"),
+    };
+    path.with_file_name(name)
+}
+
+fn rotated_path(path: &Path, time: &DateTime<Local>) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(time.format(".%Y%m%d-%H%M%S%.3f").to_string());
+    PathBuf::from(rotated)
+}
+
+/// Rotated path which neither exists nor was compressed already, several rotations might happen within a millisecond
+fn unused_rotated_path(path: &Path, time: &DateTime<Local>) -> PathBuf {
+    let rotated = rotated_path(path, time);
+    let is_used = |path: &Path| path.exists() || with_suffix(path, ".gz").exists();
+    if !is_used(&rotated) {
+        return rotated;
+    }
+    (1..)
+        .map(|counter| with_suffix(&rotated, &format!("-{counter}")))
+        .find(|path| !is_used(path))
+        .expect("some counter should be unused")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Replace the file with a `.gz` of it.
+///
+/// The `.gz` only appears once it is complete, an interrupted compression leaves the file and a `.gz.tmp`.
+fn compress(path: &Path) -> io::Result<()> {
+    let compressed = with_suffix(path, ".gz");
+    let temporary = with_suffix(path, ".gz.tmp");
+    let mut input = File::open(path)?;
+    let mut encoder = GzEncoder::new(File::create(&temporary)?, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    fs::rename(&temporary, &compressed)?;
+    fs::remove_file(path)
+}
+
+#[test]
+fn group_path_works() {
+    let path = Path::new("logs/demo.log");
+    assert_eq!(group_path(path, ""), path);
+    assert_eq!(
+        group_path(path, "sensors"),
+        Path::new("logs/demo.sensors.log")
+    );
+    assert_eq!(group_path(path, "@"), Path::new("logs/demo._.log"));
+    assert_eq!(group_path(Path::new("demo"), "a"), Path::new("demo.a"));
+}
+
+#[test]
+fn rotated_path_works() {
+    use chrono::TimeZone;
+    let time = Local
+        .from_local_datetime(&crate::zenoh_client::Time::datetime_example())
+        .unwrap();
+    assert_eq!(
+        rotated_path(Path::new("logs/demo.log"), &time),
+        Path::new("logs/demo.log.19961219-163957.000")
+    );
+}
+
+#[test]
+fn rotates_by_size() {
+    let dir = std::env::temp_dir().join(format!("zenohui-log-file-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let mut files = LogFiles {
+        path: dir.join("demo.csv"),
+        max_size: Some(25),
+        max_age: None,
+        gzip: false,
+        split_by_key: true,
+        header: Some("key,value"),
+        open: HashMap::new(),
+        compressing: Vec::new(),
+    };
+    files.write_line("a/b", "a/b,1").unwrap();
+    files.write_line("a/b", "a/b,2").unwrap();
+    files.write_line("c", "c,1").unwrap();
+    files.write_line("a/b", "a/b,3").unwrap();
+
+    let a = dir.join("demo.a.csv");
+    assert_eq!(fs::read_to_string(&a).unwrap(), "key,value\na/b,3\n");
+    assert_eq!(
+        fs::read_to_string(dir.join("demo.c.csv")).unwrap(),
+        "key,value\nc,1\n"
+    );
+    let rotated = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().contains("demo.a.csv."))
+        .collect::<Vec<_>>();
+    assert_eq!(rotated.len(), 1);
+    assert_eq!(
+        fs::read_to_string(&rotated[0]).unwrap(),
+        "key,value\na/b,1\na/b,2\n"
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn rotated_path_is_not_reused() {
+    let dir = std::env::temp_dir().join(format!("zenohui-log-rotate-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("demo.log");
+    let time = Local::now();
+    let rotated = rotated_path(&path, &time);
+    assert_eq!(unused_rotated_path(&path, &time), rotated);
+
+    fs::write(&rotated, "").unwrap();
+    fs::write(with_suffix(&rotated, "-1.gz"), "").unwrap();
+    assert_eq!(
+        unused_rotated_path(&path, &time),
+        with_suffix(&rotated, "-2")
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compress_replaces_the_file() {
+    use std::io::Read;
+    let dir = std::env::temp_dir().join(format!("zenohui-log-gzip-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("demo.log.1");
+    fs::write(&path, "a/b,1\n").unwrap();
+
+    compress(&path).unwrap();
+    assert!(!path.exists());
+    assert!(!with_suffix(&path, ".gz.tmp").exists());
+    let mut content = String::new();
+    flate2::read::GzDecoder::new(File::open(with_suffix(&path, ".gz")).unwrap())
+        .read_to_string(&mut content)
+        .unwrap();
+    assert_eq!(content, "a/b,1\n");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
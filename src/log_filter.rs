@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use regex::Regex;
+use zenoh::key_expr::{keyexpr, OwnedKeyExpr};
+use zenoh::sample::SampleKind;
+
+use crate::changes::{ChangesOnly, Tolerance};
+use crate::cli::{KindFilter, LogFilterOptions};
+use crate::payload::{Condition, JsonSelector, Payload};
+
+/// Decides which samples are logged
+pub struct LogFilter {
+    kind: Option<SampleKind>,
+    grep: Option<Regex>,
+    conditions: Vec<(Vec<JsonSelector>, Condition)>,
+    exclude: Vec<OwnedKeyExpr>,
+    sample_every: Option<Duration>,
+    last_logged: HashMap<String, Instant>,
+    changes_only: Option<ChangesOnly>,
+}
+
+impl LogFilter {
+    pub fn new(options: &LogFilterOptions) -> anyhow::Result<Self> {
+        let grep = options
+            .grep
+            .as_deref()
+            .map(|grep| {
+                Regex::new(grep).map_err(|err| anyhow::anyhow!("Invalid --grep {grep:?}: {err}"))
+            })
+            .transpose()?;
+        let conditions = options
+            .conditions
+            .iter()
+            .map(|condition| {
+                parse_where(condition)
+                    .map_err(|err| anyhow::anyhow!("Invalid --where {condition:?}: {err}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let exclude = options
+            .exclude
+            .iter()
+            .map(|exclude| {
+                exclude
+                    .parse::<OwnedKeyExpr>()
+                    .map_err(|err| anyhow::anyhow!("Invalid --exclude {exclude:?}: {err}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let tolerance = options
+            .tolerance
+            .map(|max_difference| {
+                let path = options.tolerance_path.as_deref().unwrap_or_default();
+                let path = JsonSelector::parse_path(path)
+                    .map_err(|err| anyhow::anyhow!("Invalid --tolerance-path {path:?}: {err}"))?;
+                anyhow::Ok(Tolerance {
+                    path,
+                    max_difference,
+                })
+            })
+            .transpose()?;
+        Ok(Self {
+            kind: options.kind.map(|kind| match kind {
+                KindFilter::Put => SampleKind::Put,
+                KindFilter::Delete => SampleKind::Delete,
+            }),
+            grep,
+            conditions,
+            exclude,
+            sample_every: options.sample_every,
+            last_logged: HashMap::new(),
+            changes_only: options.changes_only.then(|| ChangesOnly::new(tolerance)),
+        })
+    }
+
+    /// Check whether the sample should be logged and remember it for `--sample-every` and `--changes-only` when it is.
+    ///
+    /// Returns `None` when the sample should not be logged.
+    /// Otherwise the amount of repeats of the key suppressed by `--changes-only` since its last logged sample.
+    pub fn matches(
+        &mut self,
+        keyexpr: &keyexpr,
+        kind: SampleKind,
+        payload: &Payload,
+        now: Instant,
+    ) -> Option<usize> {
+        if self.kind.is_some_and(|expected| expected != kind) {
+            return None;
+        }
+        if self.exclude.iter().any(|exclude| exclude.includes(keyexpr)) {
+            return None;
+        }
+        if let Some(grep) = &self.grep {
+            if !grep.is_match(&payload.to_string()) {
+                return None;
+            }
+        }
+        let conditions_match = self.conditions.iter().all(|(path, condition)| {
+            payload
+                .select(path)
+                .is_some_and(|value| condition.matches(&value))
+        });
+        if !conditions_match {
+            return None;
+        }
+        if let Some(sample_every) = self.sample_every {
+            let last_logged = self.last_logged.get(keyexpr.as_str());
+            if last_logged.is_some_and(|last| now.saturating_duration_since(*last) < sample_every) {
+                return None;
+            }
+        }
+        // Only remember samples which are actually logged
+        let repeats = match &mut self.changes_only {
+            Some(changes_only) => changes_only.observe(keyexpr.as_str(), kind, payload)?,
+            None => 0,
+        };
+        if self.sample_every.is_some() {
+            self.last_logged.insert(keyexpr.as_str().to_owned(), now);
+        }
+        Some(repeats)
+    }
+
+    /// Repeats suppressed by `--changes-only` which were not reported yet, see [`ChangesOnly::take_pending`]
+    pub fn take_pending_repeats(&mut self) -> Vec<(String, usize)> {
+        self.changes_only
+            .as_mut()
+            .map(ChangesOnly::take_pending)
+            .unwrap_or_default()
+    }
+}
+
+/// Parse a condition like `.temp > 20` into the JSON path and the [`Condition`].
+///
+/// The path ends at the first whitespace or comparison operator outside of a quoted key.
+fn parse_where(predicate: &str) -> anyhow::Result<(Vec<JsonSelector>, Condition)> {
+    let predicate = predicate.trim();
+    let path_end = JsonSelector::path_end(predicate, |char| {
+        char.is_whitespace() || matches!(char, '<' | '>' | '=' | '!')
+    })
+    .unwrap_or(predicate.len());
+    let (path, condition) = predicate.split_at(path_end);
+    Ok((
+        JsonSelector::parse_path(path)?,
+        Condition::parse(condition)?,
+    ))
+}
+
+#[cfg(test)]
+fn filter(options: &[&str]) -> LogFilter {
+    use clap::Parser;
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        filter: LogFilterOptions,
+    }
+    let args = std::iter::once("log").chain(options.iter().copied());
+    LogFilter::new(&Cli::parse_from(args).filter).unwrap()
+}
+
+#[cfg(test)]
+fn payload(json: serde_json::Value) -> Payload {
+    Payload::Json(json)
+}
+
+#[test]
+fn parse_where_works() {
+    let (path, condition) = parse_where(".temp > 20").unwrap();
+    assert_eq!(path, [JsonSelector::ObjectKey("temp".to_owned())]);
+    assert_eq!(condition, Condition::parse("> 20").unwrap());
+
+    let (path, condition) = parse_where(r#".["a b"]>=1"#).unwrap();
+    assert_eq!(path, [JsonSelector::ObjectKey("a b".to_owned())]);
+    assert_eq!(condition, Condition::parse(">= 1").unwrap());
+
+    let (path, _) = parse_where("== on").unwrap();
+    assert!(path.is_empty());
+
+    assert!(parse_where(".temp").is_err());
+    assert!(parse_where(".temp ~ 20").is_err());
+}
+
+#[test]
+fn without_options_everything_matches() {
+    let mut filter = filter(&[]);
+    let key = keyexpr::new("a/b").unwrap();
+    let now = Instant::now();
+    assert!(filter
+        .matches(key, SampleKind::Put, &payload(serde_json::json!(1)), now)
+        .is_some());
+    assert!(filter
+        .matches(key, SampleKind::Delete, &Payload::String("".into()), now)
+        .is_some());
+}
+
+#[test]
+fn kind_exclude_grep_and_where() {
+    let mut filter = filter(&[
+        "--kind",
+        "put",
+        "--exclude",
+        "a/private/**",
+        "--grep",
+        "^\\{.*temp",
+        "--where",
+        ".temp > 20",
+    ]);
+    let now = Instant::now();
+    let warm = payload(serde_json::json!({"temp": 22}));
+    let cold = payload(serde_json::json!({"temp": 18}));
+    let public = keyexpr::new("a/public").unwrap();
+    let private = keyexpr::new("a/private/b").unwrap();
+    assert!(filter
+        .matches(public, SampleKind::Put, &warm, now)
+        .is_some());
+    assert!(filter
+        .matches(public, SampleKind::Put, &cold, now)
+        .is_none());
+    assert!(filter
+        .matches(public, SampleKind::Delete, &warm, now)
+        .is_none());
+    assert!(filter
+        .matches(private, SampleKind::Put, &warm, now)
+        .is_none());
+    assert!(filter
+        .matches(public, SampleKind::Put, &Payload::String("22".into()), now)
+        .is_none());
+}
+
+#[test]
+fn sample_every_throttles_per_key() {
+    let mut filter = filter(&["--sample-every", "1s"]);
+    let start = Instant::now();
+    let value = payload(serde_json::json!(1));
+    let a = keyexpr::new("a").unwrap();
+    let b = keyexpr::new("b").unwrap();
+    assert!(filter.matches(a, SampleKind::Put, &value, start).is_some());
+    assert!(filter.matches(b, SampleKind::Put, &value, start).is_some());
+    let later = start + Duration::from_millis(500);
+    assert!(filter.matches(a, SampleKind::Put, &value, later).is_none());
+    let much_later = start + Duration::from_secs(1);
+    assert!(filter
+        .matches(a, SampleKind::Put, &value, much_later)
+        .is_some());
+}
+
+#[test]
+fn changes_only_with_tolerance() {
+    let mut filter = filter(&[
+        "--changes-only",
+        "--tolerance",
+        "0.5",
+        "--tolerance-path",
+        ".temp",
+    ]);
+    let now = Instant::now();
+    let key = keyexpr::new("a").unwrap();
+    let temp = |temp: f64| payload(serde_json::json!({ "temp": temp }));
+    assert_eq!(
+        filter.matches(key, SampleKind::Put, &temp(20.0), now),
+        Some(0)
+    );
+    assert_eq!(filter.matches(key, SampleKind::Put, &temp(20.0), now), None);
+    assert_eq!(filter.matches(key, SampleKind::Put, &temp(20.4), now), None);
+    assert_eq!(
+        filter.matches(key, SampleKind::Put, &temp(21.0), now),
+        Some(2)
+    );
+}
@@ -0,0 +1,342 @@
+use chrono::{Local, NaiveDateTime, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::cli::TimeFormat;
+use crate::payload::{JsonSelector, Payload};
+use crate::zenoh_client::Time;
+
+/// A received sample to be logged
+pub struct Record<'a> {
+    pub time: Time,
+    pub kind: &'static str,
+    pub keyexpr: &'a str,
+    pub size: usize,
+    pub payload: &'a Payload,
+}
+
+#[derive(Serialize)]
+struct JsonLog<'a> {
+    time: serde_json::Value,
+    kind: &'static str,
+    keyexpr: &'a str,
+    size: usize,
+    payload: &'a Payload,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LogFormat {
+    Text,
+    Json,
+    Csv,
+    Tsv,
+    Logfmt,
+    Template(Vec<TemplatePart>),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TemplatePart {
+    Literal(String),
+    Time,
+    Kind,
+    Key,
+    Size,
+    /// Empty path for the whole payload
+    Payload(Vec<JsonSelector>),
+}
+
+impl LogFormat {
+    pub fn parse(format: &str) -> anyhow::Result<Self> {
+        Ok(match format {
+            "text" => Self::Text,
+            "json" => Self::Json,
+            "csv" => Self::Csv,
+            "tsv" => Self::Tsv,
+            "logfmt" => Self::Logfmt,
+            template if template.contains('{') => Self::Template(parse_template(template)?),
+            _ => anyhow::bail!(
+                "Unknown format {format}. Use text, json, csv, tsv, logfmt or a template like '{{time}} {{key}} {{payload}}'"
+            ),
+        })
+    }
+
+    /// Line to print before the first record
+    pub const fn header(&self) -> Option<&'static str> {
+        match self {
+            Self::Csv => Some("time,kind,key,size,payload"),
+            Self::Tsv => Some("time\tkind\tkey\tsize\tpayload"),
+            _ => None,
+        }
+    }
+
+    pub fn format(&self, record: &Record, time_format: TimeFormat) -> String {
+        let Record {
+            time,
+            kind,
+            keyexpr,
+            size,
+            payload,
+        } = record;
+        match self {
+            Self::Text => {
+                let time = format_time(time, time_format);
+                format!("{time:12} Kind:{kind:6} {keyexpr:50} Payload({size:>3}): {payload}")
+            }
+            Self::Json => serde_json::to_string(&JsonLog {
+                time: json_time(time, time_format),
+                kind,
+                keyexpr,
+                size: *size,
+                payload,
+            })
+            .expect("Should be able to format log line as JSON"),
+            Self::Csv => fields(record, time_format)
+                .map(|(_, value)| csv_field(&value))
+                .join(","),
+            Self::Tsv => fields(record, time_format)
+                .map(|(_, value)| tsv_field(&value))
+                .join("\t"),
+            Self::Logfmt => fields(record, time_format)
+                .map(|(key, value)| format!("{key}={}", logfmt_value(&value)))
+                .join(" "),
+            Self::Template(parts) => parts
+                .iter()
+                .map(|part| match part {
+                    TemplatePart::Literal(literal) => literal.clone(),
+                    TemplatePart::Time => format_time(time, time_format),
+                    TemplatePart::Kind => (*kind).to_owned(),
+                    TemplatePart::Key => (*keyexpr).to_owned(),
+                    TemplatePart::Size => size.to_string(),
+                    TemplatePart::Payload(path) if path.is_empty() => payload.to_string(),
+                    TemplatePart::Payload(path) => payload
+                        .select(path)
+                        .map(|value| Payload::selected(value).to_string())
+                        .unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Fields of the csv, tsv and logfmt formats
+fn fields(record: &Record, time_format: TimeFormat) -> [(&'static str, String); 5] {
+    [
+        ("time", format_time(&record.time, time_format)),
+        ("kind", record.kind.to_owned()),
+        ("key", record.keyexpr.to_owned()),
+        ("size", record.size.to_string()),
+        ("payload", record.payload.to_string()),
+    ]
+}
+
+/// Parse a template like `{time} {key} {payload.temp}`.
+///
+/// `{{` and `}}` are literal braces.
+fn parse_template(template: &str) -> anyhow::Result<Vec<TemplatePart>> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+    while let Some(char) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("{{") {
+            literal.push('{');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("}}") {
+            literal.push('}');
+            rest = after;
+        } else if char == '{' {
+            // Braces in quoted keys like `{payload["a}b"]}` do not close the placeholder
+            let end = JsonSelector::path_end(rest, |char| char == '}')
+                .ok_or_else(|| anyhow::anyhow!("Missing }} in template {template}"))?;
+            if !literal.is_empty() {
+                parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+            }
+            parts.push(parse_placeholder(rest[1..end].trim())?);
+            rest = &rest[end + 1..];
+        } else if char == '}' {
+            anyhow::bail!("Unexpected }} in template {template}. Use }}}} for a literal one.");
+        } else {
+            literal.push(char);
+            rest = &rest[char.len_utf8()..];
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(TemplatePart::Literal(literal));
+    }
+    Ok(parts)
+}
+
+fn parse_placeholder(placeholder: &str) -> anyhow::Result<TemplatePart> {
+    Ok(match placeholder {
+        "time" => TemplatePart::Time,
+        "kind" => TemplatePart::Kind,
+        "key" | "keyexpr" => TemplatePart::Key,
+        "size" => TemplatePart::Size,
+        _ => match placeholder.strip_prefix("payload") {
+            Some(path) if path.is_empty() || path.starts_with(['.', '[']) => {
+                TemplatePart::Payload(JsonSelector::parse_path(path)?)
+            }
+            _ => anyhow::bail!(
+                "Unknown placeholder {{{placeholder}}}. Use time, kind, key, size, payload or a path like payload.temp"
+            ),
+        },
+    })
+}
+
+fn local(time: &NaiveDateTime) -> Option<chrono::DateTime<Local>> {
+    time.and_local_timezone(Local).earliest()
+}
+
+pub fn format_time(time: &Time, format: TimeFormat) -> String {
+    let Some(local) = time.as_optional().and_then(local) else {
+        return time.to_string();
+    };
+    match format {
+        TimeFormat::Local => time.to_string(),
+        TimeFormat::Utc => local
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Millis, true),
+        TimeFormat::Rfc3339 => local.to_rfc3339_opts(SecondsFormat::Millis, false),
+        TimeFormat::EpochMs => local.timestamp_millis().to_string(),
+    }
+}
+
+/// Keeps the naive local time of [`Time`] for the default local format
+fn json_time(time: &Time, format: TimeFormat) -> serde_json::Value {
+    match (time, format) {
+        (Time::Unknown, _) => serde_json::Value::Null,
+        (Time::Local(_), TimeFormat::Local) => {
+            serde_json::to_value(time).expect("Should be able to serialize the time")
+        }
+        (Time::Local(naive), TimeFormat::EpochMs) => local(naive)
+            .map_or(serde_json::Value::Null, |local| {
+                local.timestamp_millis().into()
+            }),
+        (Time::Local(_), TimeFormat::Utc | TimeFormat::Rfc3339) => format_time(time, format).into(),
+    }
+}
+
+/// Quote as defined in RFC 4180 when needed
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Tabs and newlines would break the columns and rows
+fn tsv_field(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn logfmt_value(value: &str) -> String {
+    if value.is_empty() || value.contains([' ', '=', '"', '\\']) || value.contains(char::is_control)
+    {
+        format!("{value:?}")
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+fn format_example(format: &str) -> String {
+    let payload = Payload::Json(serde_json::json!([21.5, "living, dining"]));
+    let record = Record {
+        time: Time::Local(Time::datetime_example()),
+        kind: "Put",
+        keyexpr: "demo/temp",
+        size: 42,
+        payload: &payload,
+    };
+    LogFormat::parse(format)
+        .unwrap()
+        .format(&record, TimeFormat::Local)
+}
+
+#[test]
+fn text_is_the_classic_line() {
+    assert_eq!(
+        format_example("text"),
+        r#"16:39:57.000 Kind:Put    demo/temp                                          Payload( 42): [21.5,"living, dining"]"#
+    );
+}
+
+#[test]
+fn json_works() {
+    assert_eq!(
+        format_example("json"),
+        r#"{"time":"1996-12-19T16:39:57","kind":"Put","keyexpr":"demo/temp","size":42,"payload":[21.5,"living, dining"]}"#
+    );
+}
+
+#[test]
+fn csv_quotes() {
+    assert_eq!(
+        format_example("csv"),
+        r#"16:39:57.000,Put,demo/temp,42,"[21.5,""living, dining""]""#
+    );
+}
+
+#[test]
+fn tsv_works() {
+    assert_eq!(
+        format_example("tsv"),
+        "16:39:57.000\tPut\tdemo/temp\t42\t[21.5,\"living, dining\"]"
+    );
+}
+
+#[test]
+fn logfmt_quotes() {
+    assert_eq!(
+        format_example("logfmt"),
+        r#"time=16:39:57.000 kind=Put key=demo/temp size=42 payload="[21.5,\"living, dining\"]""#
+    );
+}
+
+#[test]
+fn template_works() {
+    assert_eq!(
+        format_example("{time} {key} {payload[0]} {payload[1]} {payload.missing}|"),
+        "16:39:57.000 demo/temp 21.5 living, dining |"
+    );
+    assert_eq!(format_example("{{{size}}}"), "{42}");
+}
+
+#[test]
+fn template_braces_in_quoted_keys() {
+    let payload = Payload::Json(serde_json::json!({"a}b": 1, "c\"}": 2}));
+    let record = Record {
+        time: Time::Unknown,
+        kind: "Put",
+        keyexpr: "demo/braces",
+        size: 2,
+        payload: &payload,
+    };
+    let line = LogFormat::parse(r#"{payload["a}b"]} {payload["c\"}"]}!"#)
+        .unwrap()
+        .format(&record, TimeFormat::Local);
+    assert_eq!(line, "1 2!");
+    assert!(LogFormat::parse(r#"{payload["a}b}"#).is_err());
+}
+
+#[test]
+fn template_fails() {
+    assert!(LogFormat::parse("{time").is_err());
+    assert!(LogFormat::parse("{time}}").is_err());
+    assert!(LogFormat::parse("{unknown}").is_err());
+    assert!(LogFormat::parse("{payloads}").is_err());
+    assert!(LogFormat::parse("{payload..a}").is_err());
+    assert!(LogFormat::parse("yaml").is_err());
+}
+
+#[test]
+fn unknown_time_is_kept() {
+    assert_eq!(format_time(&Time::Unknown, TimeFormat::EpochMs), "UNKNOWN");
+    assert_eq!(
+        json_time(&Time::Unknown, TimeFormat::Utc),
+        serde_json::Value::Null
+    );
+}
@@ -1,31 +1,145 @@
+use std::process::ExitCode;
 use std::sync::Arc;
 
 use clap::Parser;
 
+mod admin;
+mod changes;
 mod clean;
 mod cli;
+mod config_file;
 mod format;
+mod info;
 mod interactive;
 mod log;
+mod log_file;
+mod log_filter;
+mod log_format;
 mod payload;
 mod publish;
 mod read_one;
+mod rules;
+mod scout;
+mod staleness;
+mod statistics;
+mod stats;
+mod watchdog;
 mod zenoh_client;
 
-fn main() -> anyhow::Result<()> {
-    let matches = cli::Cli::parse();
-    let (session_info, session) = zenoh_client::connect(matches.zenoh_connection)?;
-    let session = Arc::new(session);
+fn main() -> anyhow::Result<ExitCode> {
+    let mut matches = cli::Cli::parse();
+    let profile = config_file::ConfigFile::load()?.into_profile(matches.profile.as_deref())?;
+    let keys = profile.keys();
+    let theme = profile.theme();
+    profile.apply(&mut matches);
 
+    let connection = matches.zenoh_connection;
     match matches.subcommands {
-        Some(cli::Subcommands::Clean { keyexpr, dry_run }) => {
-            clean::clean(session.as_ref(), &keyexpr, dry_run)?;
+        Some(cli::Subcommands::Admin { path, timeout }) => {
+            let (_, session) = connect(connection)?;
+            admin::show(session.as_ref(), &path, timeout)?;
         }
-        Some(cli::Subcommands::Log { keyexpr, json }) => {
-            log::show(Arc::clone(&session), keyexpr, json)?;
+        Some(cli::Subcommands::Clean {
+            keyexpr,
+            dry_run,
+            query,
+            listen_for,
+            yes,
+        }) => {
+            let (_, session) = connect(connection)?;
+            let discovery = clean::Discovery {
+                query,
+                listen: listen_for,
+            };
+            clean::clean(session.as_ref(), &keyexpr, &discovery, dry_run, yes)?;
         }
-        Some(cli::Subcommands::ReadOne { keyexpr, pretty }) => {
-            read_one::show(Arc::clone(&session), keyexpr, pretty)?;
+        Some(cli::Subcommands::Info { json }) => {
+            let (session_info, session) = connect(connection)?;
+            info::show(session.as_ref(), &session_info, json);
+        }
+        Some(cli::Subcommands::Log {
+            keyexpr,
+            json,
+            format,
+            time_format,
+            select,
+            filter,
+            file,
+            rules,
+        }) => {
+            let filter = log_filter::LogFilter::new(&filter)?;
+            let format = if json {
+                log_format::LogFormat::Json
+            } else {
+                log_format::LogFormat::parse(format.as_deref().unwrap_or("text"))?
+            };
+            let select = select
+                .as_deref()
+                .map(payload::JsonSelector::parse_path)
+                .transpose()?;
+            let rules = rules.as_deref().map(rules::Rules::load).transpose()?;
+            let files = log_file::LogFiles::new(file, format.header());
+            let output = log::Output {
+                format,
+                time_format,
+                select,
+                files,
+            };
+            let (_, session) = connect(connection)?;
+            log::show(&session, keyexpr, output, filter, rules)?;
+        }
+        Some(cli::Subcommands::ReadOne {
+            keyexpr,
+            pretty,
+            select,
+            timeout,
+            count,
+            include_deletes,
+            query,
+        }) => {
+            let output = read_one::Output {
+                pretty,
+                select: select
+                    .as_deref()
+                    .map(payload::JsonSelector::parse_path)
+                    .transpose()?,
+            };
+            let (_, session) = connect(connection)?;
+            let outcome = read_one::show(
+                &session,
+                keyexpr,
+                &output,
+                timeout,
+                count,
+                include_deletes,
+                query,
+            )?;
+            if outcome == read_one::Outcome::TimedOut {
+                eprintln!("Timed out waiting for samples");
+                return Ok(ExitCode::from(read_one::EXIT_CODE_TIMEOUT));
+            }
+        }
+        Some(cli::Subcommands::Stats {
+            keyexpr,
+            duration,
+            gap_threshold,
+        }) => {
+            let (_, session) = connect(connection)?;
+            let gap_threshold = gap_threshold.unwrap_or(statistics::DEFAULT_GAP_THRESHOLD);
+            stats::show(&session, keyexpr, duration, gap_threshold)?;
+        }
+        Some(cli::Subcommands::Watchdog {
+            keyexpr,
+            stale,
+            exit_on_stale,
+        }) => {
+            let (_, session) = connect(connection)?;
+            watchdog::watch(&session, keyexpr, (&stale).into(), exit_on_stale)?;
+        }
+        // Scouting happens without a session
+        Some(cli::Subcommands::Scout { duration, json }) => {
+            let config = zenoh_client::base_config(&connection)?;
+            scout::show(config, duration, json)?;
         }
         Some(cli::Subcommands::Publish { keyexpr, payload }) => {
             let payload = payload.map_or_else(
@@ -39,17 +153,42 @@ fn main() -> anyhow::Result<()> {
                 },
                 String::into_bytes,
             );
+            let (_, session) = connect(connection)?;
             publish::send(session.as_ref(), &keyexpr, payload)?;
         }
         None => {
+            let rules = matches
+                .rules
+                .as_deref()
+                .map(rules::Rules::load)
+                .transpose()?;
+            let (session_info, session) = connect(connection)?;
             interactive::show(
-                Arc::clone(&session),
+                session,
                 &session_info,
                 matches.keyexpr,
-                matches.payload_size_limit,
+                matches
+                    .payload_size_limit
+                    .unwrap_or(config_file::DEFAULT_PAYLOAD_SIZE_LIMIT),
+                matches.retention,
+                matches
+                    .gap_threshold
+                    .unwrap_or(statistics::DEFAULT_GAP_THRESHOLD),
+                (&matches.stale).into(),
+                rules,
+                &keys,
+                theme.as_ref(),
             )?;
         }
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Only the subcommands which need a session open one
+fn connect(
+    connection: cli::ZenohConnection,
+) -> anyhow::Result<(zenoh_client::SessionInfo, Arc<zenoh::Session>)> {
+    let (session_info, session) = zenoh_client::connect(connection)?;
+    Ok((session_info, Arc::new(session)))
 }
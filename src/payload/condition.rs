@@ -0,0 +1,210 @@
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Contains,
+    Equal,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    NotEqual,
+}
+
+/// Comparison of a payload value with a fixed operand like `> 20` or `== "on"`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    operator: Operator,
+    operand: String,
+    /// Only set when the operand is an unquoted number
+    number: Option<f64>,
+}
+
+impl Condition {
+    /// Parse a condition like `> 20`, `!= "on"` or `contains error`.
+    ///
+    /// Supported operators are `<`, `<=`, `>`, `>=`, `==`, `!=` and `contains`.
+    /// Quoted operands are always compared as text.
+    pub fn parse(condition: &str) -> anyhow::Result<Self> {
+        const OPERATORS: [(&str, Operator); 7] = [
+            ("<=", Operator::LessOrEqual),
+            (">=", Operator::GreaterOrEqual),
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("<", Operator::Less),
+            (">", Operator::Greater),
+            ("contains ", Operator::Contains),
+        ];
+
+        let condition = condition.trim();
+        let (operator, operand) = OPERATORS
+            .iter()
+            .find_map(|(prefix, operator)| {
+                condition
+                    .strip_prefix(prefix)
+                    .map(|operand| (*operator, operand.trim()))
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Condition {condition:?} does not start with one of <, <=, >, >=, ==, != or contains"
+                )
+            })?;
+        anyhow::ensure!(
+            !operand.is_empty(),
+            "Condition {condition:?} has no value to compare with"
+        );
+
+        let quoted = operand
+            .strip_prefix('"')
+            .and_then(|operand| operand.strip_suffix('"'));
+        let (operand, number) = quoted.map_or_else(
+            || (operand, operand.parse::<f64>().ok()),
+            |quoted| (quoted, None),
+        );
+        if matches!(
+            operator,
+            Operator::Greater | Operator::GreaterOrEqual | Operator::Less | Operator::LessOrEqual
+        ) {
+            anyhow::ensure!(
+                number.is_some(),
+                "Condition {condition:?} needs a number to compare with"
+            );
+        }
+
+        Ok(Self {
+            operator,
+            operand: operand.to_owned(),
+            number,
+        })
+    }
+
+    /// Numbers are compared exactly as the operand is written literally in the rule
+    #[allow(clippy::float_cmp)]
+    pub fn matches(&self, value: &Value) -> bool {
+        let numbers = self.number.zip(as_number(value));
+        match self.operator {
+            Operator::Contains => as_text(value).contains(&self.operand),
+            Operator::Equal => numbers.map_or_else(
+                || as_text(value) == self.operand,
+                |(operand, value)| value == operand,
+            ),
+            Operator::NotEqual => numbers.map_or_else(
+                || as_text(value) != self.operand,
+                |(operand, value)| value != operand,
+            ),
+            Operator::Greater => numbers.is_some_and(|(operand, value)| value > operand),
+            Operator::GreaterOrEqual => numbers.is_some_and(|(operand, value)| value >= operand),
+            Operator::Less => numbers.is_some_and(|(operand, value)| value < operand),
+            Operator::LessOrEqual => numbers.is_some_and(|(operand, value)| value <= operand),
+        }
+    }
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operator = match self.operator {
+            Operator::Contains => "contains",
+            Operator::Equal => "==",
+            Operator::Greater => ">",
+            Operator::GreaterOrEqual => ">=",
+            Operator::Less => "<",
+            Operator::LessOrEqual => "<=",
+            Operator::NotEqual => "!=",
+        };
+        if self.number.is_some() {
+            write!(fmt, "{operator} {}", self.operand)
+        } else {
+            write!(fmt, "{operator} {:?}", self.operand)
+        }
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Bool(true) => Some(1.0),
+        Value::Bool(false) => Some(0.0),
+        Value::Number(number) => number.as_f64(),
+        Value::String(str) => str.split_whitespace().next()?.parse().ok(),
+        Value::Null | Value::Array(_) | Value::Object(_) => None,
+    }
+}
+
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(str) => str.clone(),
+        _ => value.to_string(),
+    }
+}
+
+#[test]
+fn parse_fails() {
+    assert!(Condition::parse("").is_err());
+    assert!(Condition::parse("20").is_err());
+    assert!(Condition::parse(">").is_err());
+    assert!(Condition::parse("> warm").is_err());
+    assert!(Condition::parse("contains").is_err());
+}
+
+#[test]
+fn display_works() {
+    assert_eq!(Condition::parse(">=20").unwrap().to_string(), ">= 20");
+    assert_eq!(Condition::parse("== on").unwrap().to_string(), "== \"on\"");
+    assert_eq!(
+        Condition::parse("contains \"a b\"").unwrap().to_string(),
+        "contains \"a b\""
+    );
+}
+
+#[test]
+fn number_comparisons() {
+    let value = serde_json::json!(21.5);
+    assert!(Condition::parse("> 20").unwrap().matches(&value));
+    assert!(Condition::parse(">= 21.5").unwrap().matches(&value));
+    assert!(!Condition::parse("< 20").unwrap().matches(&value));
+    assert!(Condition::parse("<= 21.5").unwrap().matches(&value));
+    assert!(Condition::parse("== 21.5").unwrap().matches(&value));
+    assert!(Condition::parse("!= 20").unwrap().matches(&value));
+}
+
+#[test]
+fn number_equality_is_exact() {
+    assert!(Condition::parse("== 0.1")
+        .unwrap()
+        .matches(&serde_json::json!(0.1)));
+    assert!(!Condition::parse("== 0")
+        .unwrap()
+        .matches(&serde_json::json!(1e-17)));
+    assert!(Condition::parse("!= 0")
+        .unwrap()
+        .matches(&serde_json::json!(1e-17)));
+    assert!(Condition::parse("== 1e20")
+        .unwrap()
+        .matches(&serde_json::json!(100_000_000_000_000_000_000.0)));
+}
+
+#[test]
+fn number_from_string() {
+    let value = serde_json::json!("21.5 °C");
+    assert!(Condition::parse("> 20").unwrap().matches(&value));
+    assert!(!Condition::parse("> 20")
+        .unwrap()
+        .matches(&serde_json::json!("warm")));
+}
+
+#[test]
+fn text_comparisons() {
+    let value = serde_json::json!("on");
+    assert!(Condition::parse("== on").unwrap().matches(&value));
+    assert!(Condition::parse("== \"on\"").unwrap().matches(&value));
+    assert!(Condition::parse("!= off").unwrap().matches(&value));
+    assert!(Condition::parse("contains o").unwrap().matches(&value));
+    assert!(!Condition::parse("contains x").unwrap().matches(&value));
+}
+
+#[test]
+fn quoted_number_is_text() {
+    let value = serde_json::json!(42);
+    assert!(Condition::parse("== \"42\"").unwrap().matches(&value));
+    assert!(Condition::parse("== 42.0").unwrap().matches(&value));
+    assert!(!Condition::parse("== \"42.0\"").unwrap().matches(&value));
+}
@@ -1,4 +1,4 @@
-#[derive(Default, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub enum JsonSelector {
     ObjectKey(String),
     ArrayIndex(usize),
@@ -7,6 +7,117 @@ pub enum JsonSelector {
 }
 
 impl JsonSelector {
+    /// Parse a path like `.sensors[0].temp` or `.a["key with.dots"]` into its segments.
+    ///
+    /// The leading dot is optional and an empty path or `.` selects the root.
+    /// Keys in brackets are JSON strings and can contain any character.
+    pub fn parse_path(path: &str) -> anyhow::Result<Vec<Self>> {
+        let path = path.trim();
+        if path.is_empty() || path == "." {
+            return Ok(Vec::new());
+        }
+        let normalized = if path.starts_with(['.', '[']) {
+            path.to_owned()
+        } else {
+            format!(".{path}")
+        };
+
+        let mut segments = Vec::new();
+        let mut rest = normalized.as_str();
+        while let Some(first) = rest.chars().next() {
+            match first {
+                '.' => {
+                    let end = rest[1..].find(['.', '[']).map_or(rest.len(), |end| end + 1);
+                    let key = &rest[1..end];
+                    anyhow::ensure!(!key.is_empty(), "Empty key in path {path}");
+                    segments.push(Self::ObjectKey(key.to_owned()));
+                    rest = &rest[end..];
+                }
+                '[' if rest[1..].trim_start().starts_with('"') => {
+                    let mut stream =
+                        serde_json::Deserializer::from_str(&rest[1..]).into_iter::<String>();
+                    let key = stream
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("Missing key in path {path}"))?
+                        .map_err(|err| {
+                            anyhow::anyhow!("Invalid quoted key in path {path}: {err}")
+                        })?;
+                    let after_key = rest[1 + stream.byte_offset()..].trim_start();
+                    let after_bracket = after_key
+                        .strip_prefix(']')
+                        .ok_or_else(|| anyhow::anyhow!("Missing ] in path {path}"))?;
+                    segments.push(Self::ObjectKey(key));
+                    rest = after_bracket;
+                }
+                '[' => {
+                    let end = rest
+                        .find(']')
+                        .ok_or_else(|| anyhow::anyhow!("Missing ] in path {path}"))?;
+                    let index = &rest[1..end];
+                    let index = index.trim().parse().map_err(|err| {
+                        anyhow::anyhow!("Invalid array index {index} in path {path}: {err}")
+                    })?;
+                    segments.push(Self::ArrayIndex(index));
+                    rest = &rest[end + 1..];
+                }
+                _ => anyhow::bail!("Unexpected {first:?} in path {path}"),
+            }
+        }
+        Ok(segments)
+    }
+
+    /// Index of the first char which is a delimiter outside of a quoted key like `["a b"]`.
+    ///
+    /// Used to find the end of a path embedded in other text, escaped quotes in keys are skipped.
+    pub fn path_end(text: &str, is_delimiter: impl Fn(char) -> bool) -> Option<usize> {
+        let mut in_quotes = false;
+        let mut escaped = false;
+        text.char_indices()
+            .find(|(_, char)| {
+                if in_quotes {
+                    if escaped {
+                        escaped = false;
+                    } else if *char == '\\' {
+                        escaped = true;
+                    } else if *char == '"' {
+                        in_quotes = false;
+                    }
+                    false
+                } else if *char == '"' {
+                    in_quotes = true;
+                    false
+                } else {
+                    is_delimiter(*char)
+                }
+            })
+            .map(|(index, _)| index)
+    }
+
+    /// Format the segments as a path which [`Self::parse_path`] understands.
+    ///
+    /// Keys which are not plain words are quoted like `["key with.dots"]`.
+    pub fn format_path(path: &[Self]) -> String {
+        let mut formatted = String::new();
+        for segment in path {
+            match segment {
+                Self::ObjectKey(key) if is_plain_key(key) => {
+                    formatted.push('.');
+                    formatted += key;
+                }
+                Self::ObjectKey(key) => {
+                    let key = serde_json::to_string(key).expect("Should be able to quote a key");
+                    formatted += &format!("[{key}]");
+                }
+                Self::ArrayIndex(index) => formatted += &format!("[{index}]"),
+                Self::None => {}
+            }
+        }
+        if formatted.is_empty() {
+            formatted.push('.');
+        }
+        formatted
+    }
+
     fn apply_json<'v>(&self, root: &'v serde_json::Value) -> Option<&'v serde_json::Value> {
         use serde_json::Value;
         match (root, self) {
@@ -57,6 +168,13 @@ impl JsonSelector {
     }
 }
 
+fn is_plain_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|char| char.is_alphanumeric() || char == '_' || char == '-')
+}
+
 impl std::fmt::Display for JsonSelector {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -67,6 +185,78 @@ impl std::fmt::Display for JsonSelector {
     }
 }
 
+#[test]
+fn parse_path_root() {
+    assert!(JsonSelector::parse_path("").unwrap().is_empty());
+    assert!(JsonSelector::parse_path(".").unwrap().is_empty());
+}
+
+#[test]
+fn parse_path_works() {
+    let expected = vec![
+        JsonSelector::ObjectKey("sensors".to_owned()),
+        JsonSelector::ArrayIndex(0),
+        JsonSelector::ObjectKey("temp".to_owned()),
+    ];
+    assert_eq!(
+        JsonSelector::parse_path(".sensors[0].temp").unwrap(),
+        expected
+    );
+    assert_eq!(
+        JsonSelector::parse_path("sensors[0].temp").unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn parse_path_fails() {
+    assert!(JsonSelector::parse_path(".a..b").is_err());
+    assert!(JsonSelector::parse_path(".a[0").is_err());
+    assert!(JsonSelector::parse_path(".a[x]").is_err());
+    assert!(JsonSelector::parse_path(".a[0]b").is_err());
+    assert!(JsonSelector::parse_path(r#".a["b"#).is_err());
+    assert!(JsonSelector::parse_path(r#".a["b""#).is_err());
+}
+
+#[test]
+fn parse_path_quoted_keys() {
+    assert_eq!(
+        JsonSelector::parse_path(r#".a["key with.dots"][3]["quote \" ]"]"#).unwrap(),
+        vec![
+            JsonSelector::ObjectKey("a".to_owned()),
+            JsonSelector::ObjectKey("key with.dots".to_owned()),
+            JsonSelector::ArrayIndex(3),
+            JsonSelector::ObjectKey("quote \" ]".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn path_end_skips_quoted_keys() {
+    let is_space = char::is_whitespace;
+    assert_eq!(JsonSelector::path_end(".a b", is_space), Some(2));
+    assert_eq!(JsonSelector::path_end(r#".["a b"] c"#, is_space), Some(8));
+    assert_eq!(
+        JsonSelector::path_end(r#".["a\" b"] c"#, is_space),
+        Some(10)
+    );
+    assert_eq!(JsonSelector::path_end(r#".["a b"]"#, is_space), None);
+}
+
+#[test]
+fn format_path_works() {
+    assert_eq!(JsonSelector::format_path(&[]), ".");
+    let path = vec![
+        JsonSelector::ObjectKey("a".to_owned()),
+        JsonSelector::ObjectKey("b".to_owned()),
+        JsonSelector::ArrayIndex(3),
+        JsonSelector::ObjectKey("key with.dots".to_owned()),
+    ];
+    let formatted = JsonSelector::format_path(&path);
+    assert_eq!(formatted, r#".a.b[3]["key with.dots"]"#);
+    assert_eq!(JsonSelector::parse_path(&formatted).unwrap(), path);
+}
+
 #[test]
 fn display_object() {
     let selector = JsonSelector::ObjectKey("foo".to_owned());
@@ -1,9 +1,11 @@
 use serde::Serialize;
 
+pub use self::condition::Condition;
 pub use self::json::tree_items as tree_items_from_json;
 pub use self::json_selector::JsonSelector;
 pub use self::messagepack::tree_items::tree_items as tree_items_from_messagepack;
 
+mod condition;
 mod json;
 mod json_selector;
 mod messagepack;
@@ -44,6 +46,32 @@ impl Payload {
     }
 }
 
+impl Payload {
+    /// Value at the given path.
+    ///
+    /// Strings have no inner structure and are only selected by the root path.
+    /// Binary payloads are never selected.
+    pub fn select(&self, path: &[JsonSelector]) -> Option<serde_json::Value> {
+        match self {
+            Self::Binary(_) => None,
+            Self::Json(json) => JsonSelector::get_json(json, path).cloned(),
+            Self::MessagePack(messagepack) => JsonSelector::get_messagepack(messagepack, path)
+                .and_then(|value| serde_json::to_value(value).ok()),
+            Self::String(str) => path
+                .is_empty()
+                .then(|| serde_json::Value::String(str.to_string())),
+        }
+    }
+
+    /// Selected value to be displayed like `jq --raw-output`: strings without quotes, everything else as JSON
+    pub fn selected(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::String(str) => Self::String(str.into()),
+            value => Self::Json(value),
+        }
+    }
+}
+
 impl std::fmt::Display for Payload {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -111,6 +139,34 @@ fn display_string_works() {
     assert_eq!(format!("{payload}"), "bar");
 }
 
+#[test]
+fn select_json_works() {
+    let payload = Payload::Json(serde_json::json!({"sensors": [{"temp": 21.5}]}));
+    let path = JsonSelector::parse_path(".sensors[0].temp").unwrap();
+    assert_eq!(payload.select(&path), Some(serde_json::json!(21.5)));
+    let path = JsonSelector::parse_path(".sensors[1]").unwrap();
+    assert_eq!(payload.select(&path), None);
+}
+
+#[test]
+fn select_messagepack_works() {
+    use rmpv::Value;
+    let payload = Payload::MessagePack(Value::Map(vec![(
+        Value::String("temp".into()),
+        Value::F64(21.5),
+    )]));
+    let path = JsonSelector::parse_path(".temp").unwrap();
+    assert_eq!(payload.select(&path), Some(serde_json::json!(21.5)));
+}
+
+#[test]
+fn select_string_only_root() {
+    let payload = Payload::String("hello".into());
+    assert_eq!(payload.select(&[]), Some(serde_json::json!("hello")));
+    let path = JsonSelector::parse_path(".foo").unwrap();
+    assert_eq!(payload.select(&path), None);
+}
+
 #[cfg(test)]
 fn json_macro(json_str: &'static str) -> Option<String> {
     match Payload::unlimited(json_str.into()) {
@@ -136,3 +192,11 @@ fn pretty_json_object_works() {
 fn pretty_json_number_works() {
     assert_eq!(json_macro("42"), Some("42".to_owned()));
 }
+
+#[test]
+fn selected_strings_are_unquoted() {
+    let payload = Payload::selected(serde_json::json!("foo"));
+    assert_eq!(format!("{payload}"), "foo");
+    let payload = Payload::selected(serde_json::json!({"temp": 21.5}));
+    assert_eq!(format!("{payload}"), r#"{"temp":21.5}"#);
+}
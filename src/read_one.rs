@@ -1,65 +1,154 @@
-use std::sync::{mpsc, Arc};
-use std::thread;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use zenoh::handlers::fifo::FifoChannel;
-use zenoh::sample::SampleKind;
+use zenoh::sample::{Sample, SampleKind};
 use zenoh::{Session, Wait};
 
-use crate::payload::Payload;
+use crate::payload::{JsonSelector, Payload};
+use crate::{format, zenoh_client};
 
-pub fn show(session: Arc<Session>, keyexprs: Vec<String>, pretty: bool) -> anyhow::Result<()> {
-    let (tx, rx) = mpsc::channel();
+/// Exit code when the timeout elapsed before enough samples were received
+pub const EXIT_CODE_TIMEOUT: u8 = 2;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Received,
+    TimedOut,
+}
+
+/// How the received samples are printed
+pub struct Output {
+    pub pretty: bool,
+    /// Only print the value at this path and skip samples without it
+    pub select: Option<Vec<JsonSelector>>,
+}
+
+pub fn show(
+    session: &Arc<Session>,
+    keyexprs: Vec<String>,
+    output: &Output,
+    timeout: Option<Duration>,
+    count: u32,
+    include_deletes: bool,
+    query: bool,
+) -> anyhow::Result<Outcome> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let one_per_line = count > 1;
+    let mut remaining = count;
+
+    // Subscribe before querying to not miss samples published in between
+    let rx = zenoh_client::subscribe_all(session, keyexprs.clone());
+
+    if query {
+        let stored = query_stored(session, &keyexprs, deadline, include_deletes)?;
+        for sample in &stored {
+            if remaining == 0 {
+                break;
+            }
+            if print(sample, output, one_per_line) {
+                remaining -= 1;
+            }
+        }
+    }
+
+    while remaining > 0 {
+        let sample = if let Some(deadline) = deadline {
+            match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(sample) => sample,
+                Err(RecvTimeoutError::Timeout) => return Ok(Outcome::TimedOut),
+                Err(RecvTimeoutError::Disconnected) => {
+                    anyhow::bail!("All subscribers stopped before enough samples were received")
+                }
+            }
+        } else {
+            rx.recv().map_err(|_| {
+                anyhow::anyhow!("All subscribers stopped before enough samples were received")
+            })?
+        };
+        if sample.kind() == SampleKind::Delete && !include_deletes {
+            continue;
+        }
+        if print(&sample, output, one_per_line) {
+            remaining -= 1;
+        }
+    }
+
+    Ok(Outcome::Received)
+}
+
+/// Get the values stored for the key expressions, e.g. by a storage
+fn query_stored(
+    session: &Session,
+    keyexprs: &[String],
+    deadline: Option<Instant>,
+    include_deletes: bool,
+) -> anyhow::Result<Vec<Sample>> {
+    let mut samples = Vec::new();
     for keyexpr in keyexprs {
-        let session = Arc::clone(&session);
-        let tx = tx.clone();
-        thread::Builder::new()
-            .name(format!("zenoh read-one {keyexpr}"))
-            .spawn(move || {
-                let subscriber = match session
-                    .declare_subscriber(&keyexpr)
-                    .with(FifoChannel::default())
-                    .wait()
-                {
-                    Ok(subscriber) => subscriber,
-                    Err(err) => {
-                        eprintln!("Failed to subscribe to {keyexpr}: {err}");
-                        return;
-                    }
-                };
-                loop {
-                    match subscriber.recv() {
-                        Ok(sample) => {
-                        if sample.kind() == SampleKind::Delete {
-                                continue;
-                            }
-                            if tx.send(sample).is_err() {
-                                break;
-                            }
-                        }
-                        Err(err) => {
-                            eprintln!("Subscriber error: {err}");
-                            break;
-                        }
+        let mut get = session.get(keyexpr);
+        if let Some(deadline) = deadline {
+            get = get.timeout(deadline.saturating_duration_since(Instant::now()));
+        }
+        let replies = get
+            .wait()
+            .map_err(|err| anyhow::anyhow!("Failed to query {keyexpr}: {err}"))?;
+        while let Ok(reply) = replies.recv() {
+            match reply.into_result() {
+                Ok(sample) => {
+                    if sample.kind() == SampleKind::Put || include_deletes {
+                        samples.push(sample);
                     }
                 }
-            })
-            .expect("should be able to spawn subscriber thread");
+                Err(err) => {
+                    let payload = Payload::unlimited(err.payload().to_bytes().to_vec());
+                    eprintln!("Query of {keyexpr} failed: {payload}");
+                }
+            }
+        }
     }
+    Ok(samples)
+}
+
+/// Returns false when the sample was skipped as the selected path is not in its payload
+fn print(sample: &Sample, output: &Output, one_per_line: bool) -> bool {
+    let payload = sample.payload().to_bytes().to_vec();
+    let selected = match &output.select {
+        Some(path) => match Payload::unlimited(payload.clone()).select(path) {
+            Some(value) => Some(Payload::selected(value)),
+            None => return false,
+        },
+        None => None,
+    };
 
-    if let Ok(sample) = rx.recv() {
-        eprintln!("{}", sample.key_expr().as_str());
-        let payload = sample.payload().to_bytes().to_vec();
-        if pretty {
-            let payload = Payload::unlimited(payload);
-            println!("{payload:#}");
+    let keyexpr = sample.key_expr().as_str();
+    if sample.kind() == SampleKind::Delete {
+        eprintln!("{keyexpr} {}", format::kind(sample.kind()));
+    } else {
+        eprintln!("{keyexpr}");
+    }
+    if let Some(value) = selected {
+        if output.pretty {
+            println!("{value:#}");
         } else {
-            use std::io::Write;
-            std::io::stdout()
-                .write_all(&payload)
+            println!("{value}");
+        }
+    } else if output.pretty {
+        let payload = Payload::unlimited(payload);
+        println!("{payload:#}");
+    } else {
+        use std::io::Write;
+        let mut stdout = std::io::stdout().lock();
+        stdout
+            .write_all(&payload)
+            .expect("Should be able to write payload to stdout");
+        if one_per_line {
+            stdout
+                .write_all(b"\n")
                 .expect("Should be able to write payload to stdout");
         }
+        // Keep the order with stderr when several payloads are printed
+        stdout.flush().expect("Should be able to flush stdout");
     }
-
-    Ok(())
+    true
 }
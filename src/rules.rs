@@ -0,0 +1,325 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+use zenoh::key_expr::{keyexpr, OwnedKeyExpr};
+
+use crate::payload::{Condition, JsonSelector, Payload};
+use crate::zenoh_client::Time;
+
+/// Content of the rules file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    /// File to append the alerts of rules with the `log` action to
+    alerts_log: Option<PathBuf>,
+    #[serde(default, rename = "rule")]
+    rules: Vec<RuleConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleConfig {
+    name: Option<String>,
+    keyexpr: String,
+    #[serde(default)]
+    path: String,
+    condition: String,
+    actions: Vec<Action>,
+    /// Shell command run by the `command` action
+    command: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    /// Highlight the key in the TUI while the condition matches
+    Highlight,
+    /// Show the alert in a banner of the TUI while the condition matches
+    Banner,
+    /// Ring the terminal bell
+    Bell,
+    /// Append the alert to the `alerts_log` file
+    Log,
+    /// Run the `command` of the rule
+    Command,
+}
+
+struct Rule {
+    name: String,
+    keyexpr: OwnedKeyExpr,
+    path: Vec<JsonSelector>,
+    condition: Condition,
+    actions: Vec<Action>,
+    command: Option<String>,
+}
+
+impl Rule {
+    fn from_config(index: usize, config: RuleConfig) -> anyhow::Result<Self> {
+        let name = config
+            .name
+            .unwrap_or_else(|| format!("{} {} {}", config.keyexpr, config.path, config.condition));
+        let context = |what: &str| format!("Rule {} ({name}) has an invalid {what}", index + 1);
+        let keyexpr = config
+            .keyexpr
+            .parse::<OwnedKeyExpr>()
+            .map_err(|err| anyhow::anyhow!("{}: {err}", context("keyexpr")))?;
+        let path = JsonSelector::parse_path(&config.path)
+            .map_err(|err| anyhow::anyhow!("{}: {err}", context("path")))?;
+        let condition = Condition::parse(&config.condition)
+            .map_err(|err| anyhow::anyhow!("{}: {err}", context("condition")))?;
+        anyhow::ensure!(
+            !config.actions.contains(&Action::Command) || config.command.is_some(),
+            "Rule {} ({name}) has the command action but no command",
+            index + 1
+        );
+        Ok(Self {
+            name,
+            keyexpr,
+            path,
+            condition,
+            actions: config.actions,
+            command: config.command,
+        })
+    }
+}
+
+pub struct Alert {
+    pub time: Time,
+    pub rule: String,
+    pub keyexpr: String,
+    pub value: String,
+    pub banner: bool,
+    /// Error while running the actions of the rule
+    pub action_error: Option<String>,
+    rule_index: usize,
+}
+
+impl std::fmt::Display for Alert {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "{}: {} = {}", self.rule, self.keyexpr, self.value)?;
+        if let Some(err) = &self.action_error {
+            write!(fmt, " (action failed: {err})")?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Rules {
+    alerts_log: Option<PathBuf>,
+    rules: Vec<Rule>,
+    /// Rule index and key of currently matching conditions
+    active: HashSet<(usize, String)>,
+}
+
+impl Rules {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|err| {
+            anyhow::anyhow!("Failed to read rules file {}: {err}", path.display())
+        })?;
+        Self::parse(&content)
+            .map_err(|err| anyhow::anyhow!("Invalid rules file {}: {err}", path.display()))
+    }
+
+    fn parse(content: &str) -> anyhow::Result<Self> {
+        let file = toml::from_str::<RulesFile>(content)?;
+        let rules = file
+            .rules
+            .into_iter()
+            .enumerate()
+            .map(|(index, config)| Rule::from_config(index, config))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        anyhow::ensure!(
+            file.alerts_log.is_some()
+                || !rules.iter().any(|rule| rule.actions.contains(&Action::Log)),
+            "The log action requires alerts_log to be set"
+        );
+        Ok(Self {
+            alerts_log: file.alerts_log,
+            rules,
+            active: HashSet::new(),
+        })
+    }
+
+    /// Whether any rule applies to the key, so the payload is worth parsing for [`Self::evaluate`]
+    pub fn applies_to(&self, keyexpr: &keyexpr) -> bool {
+        self.rules.iter().any(|rule| rule.keyexpr.includes(keyexpr))
+    }
+
+    /// Evaluate the rules matching the key and run the actions of rules that started to match.
+    ///
+    /// Returns the alerts of the rules that started to match.
+    pub fn evaluate(&mut self, keyexpr: &keyexpr, payload: &Payload) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            if !rule.keyexpr.includes(keyexpr) {
+                continue;
+            }
+            let value = payload.select(&rule.path);
+            let is_match = value
+                .as_ref()
+                .is_some_and(|value| rule.condition.matches(value));
+            let key = (index, keyexpr.as_str().to_owned());
+            if !is_match {
+                self.active.remove(&key);
+                continue;
+            }
+            if !self.active.insert(key) {
+                continue; // Already alerted
+            }
+
+            let mut alert = Alert {
+                time: Time::new_now(),
+                rule: rule.name.clone(),
+                keyexpr: keyexpr.as_str().to_owned(),
+                value: value.map_or_else(String::new, |value| value.to_string()),
+                banner: rule.actions.contains(&Action::Banner),
+                action_error: None,
+                rule_index: index,
+            };
+            alert.action_error = self
+                .run_actions(rule, &alert)
+                .err()
+                .map(|err| err.to_string());
+            alerts.push(alert);
+        }
+        alerts
+    }
+
+    fn run_actions(&self, rule: &Rule, alert: &Alert) -> anyhow::Result<()> {
+        if rule.actions.contains(&Action::Bell) {
+            // stderr keeps stdout usable for piping in log mode
+            eprint!("\x07");
+        }
+        if rule.actions.contains(&Action::Log) {
+            if let Some(path) = &self.alerts_log {
+                let mut file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)?;
+                writeln!(file, "{} {alert}", alert.time)?;
+            }
+        }
+        if rule.actions.contains(&Action::Command) {
+            if let Some(command) = &rule.command {
+                let mut child = shell(command)
+                    .env("ZENOHUI_RULE", &alert.rule)
+                    .env("ZENOHUI_KEYEXPR", &alert.keyexpr)
+                    .env("ZENOHUI_VALUE", &alert.value)
+                    .stdin(std::process::Stdio::null())
+                    .stdout(std::process::Stdio::null())
+                    .stderr(std::process::Stdio::null())
+                    .spawn()?;
+                std::thread::spawn(move || child.wait());
+            }
+        }
+        Ok(())
+    }
+
+    /// The condition of the alert still matches
+    pub fn is_active(&self, alert: &Alert) -> bool {
+        self.active
+            .contains(&(alert.rule_index, alert.keyexpr.clone()))
+    }
+
+    /// A rule with the highlight action currently matches the key
+    pub fn is_highlighted(&self, keyexpr: &str) -> bool {
+        self.active.iter().any(|(index, key)| {
+            key == keyexpr && self.rules[*index].actions.contains(&Action::Highlight)
+        })
+    }
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(not(unix))]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = r#"
+[[rule]]
+name = "too hot"
+keyexpr = "lab/*/temp"
+path = ".value"
+condition = "> 30"
+actions = ["highlight", "banner"]
+"#;
+
+#[test]
+fn parse_example() {
+    let rules = Rules::parse(EXAMPLE).unwrap();
+    assert_eq!(rules.rules.len(), 1);
+    assert_eq!(rules.rules[0].name, "too hot");
+    assert_eq!(rules.rules[0].actions, [Action::Highlight, Action::Banner]);
+}
+
+#[test]
+fn parse_requires_alerts_log() {
+    let content = r#"
+[[rule]]
+keyexpr = "**"
+condition = "> 30"
+actions = ["log"]
+"#;
+    assert!(Rules::parse(content).is_err());
+}
+
+#[test]
+fn parse_requires_command() {
+    let content = r#"
+[[rule]]
+keyexpr = "**"
+condition = "> 30"
+actions = ["command"]
+"#;
+    assert!(Rules::parse(content).is_err());
+}
+
+#[test]
+fn applies_to_matching_keys() {
+    let rules = Rules::parse(EXAMPLE).unwrap();
+    assert!(rules.applies_to(keyexpr::new("lab/rig1/temp").unwrap()));
+    assert!(!rules.applies_to(keyexpr::new("lab/rig1/humidity").unwrap()));
+}
+
+#[test]
+fn alerts_once_until_cleared() {
+    let mut rules = Rules::parse(EXAMPLE).unwrap();
+    let key = keyexpr::new("lab/rig1/temp").unwrap();
+    let hot = Payload::Json(serde_json::json!({"value": 35}));
+    let cold = Payload::Json(serde_json::json!({"value": 20}));
+
+    let alerts = rules.evaluate(key, &hot);
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].to_string(), "too hot: lab/rig1/temp = 35");
+    assert!(alerts[0].banner);
+    assert!(rules.is_active(&alerts[0]));
+    assert!(rules.is_highlighted("lab/rig1/temp"));
+
+    assert!(rules.evaluate(key, &hot).is_empty());
+
+    assert!(rules.evaluate(key, &cold).is_empty());
+    assert!(!rules.is_active(&alerts[0]));
+    assert!(!rules.is_highlighted("lab/rig1/temp"));
+
+    assert_eq!(rules.evaluate(key, &hot).len(), 1);
+}
+
+#[test]
+fn ignores_other_keys() {
+    let mut rules = Rules::parse(EXAMPLE).unwrap();
+    let key = keyexpr::new("lab/rig1/humidity").unwrap();
+    let hot = Payload::Json(serde_json::json!({"value": 35}));
+    assert!(rules.evaluate(key, &hot).is_empty());
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use zenoh::config::WhatAmI;
+use zenoh::Config;
+
+use crate::zenoh_client::discover;
+
+pub fn show(config: Config, duration: Duration, json: bool) -> anyhow::Result<()> {
+    let nodes = discover(config, WhatAmI::Router | WhatAmI::Peer, duration, false)?;
+
+    if json {
+        for node in &nodes {
+            let json = serde_json::to_string(node).expect("Should be able to format node as JSON");
+            println!("{json}");
+        }
+    } else if nodes.is_empty() {
+        eprintln!(
+            "No routers or peers found within {}",
+            humantime::format_duration(duration)
+        );
+    } else {
+        println!("{:32} {:7} LOCATORS", "ZID", "WHATAMI");
+        for node in &nodes {
+            println!(
+                "{:32} {:7} {}",
+                node.zid,
+                node.whatami,
+                node.locators.join(" ")
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::time::{Duration, Instant};
+
+use crate::cli::StaleOptions;
+
+/// Weight of the newest interval when updating the typical period
+const SMOOTHING: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy)]
+pub struct StaleSettings {
+    /// Stale after not being seen for this multiple of the typical period
+    pub factor: f64,
+    /// Stale after not being seen for this long regardless of the typical period
+    pub timeout: Option<Duration>,
+}
+
+impl Default for StaleSettings {
+    fn default() -> Self {
+        Self {
+            factor: 3.0,
+            timeout: None,
+        }
+    }
+}
+
+impl From<&StaleOptions> for StaleSettings {
+    fn from(options: &StaleOptions) -> Self {
+        Self {
+            factor: options
+                .stale_factor
+                .unwrap_or_else(|| Self::default().factor),
+            timeout: options.stale_timeout,
+        }
+    }
+}
+
+/// Tracks when a key was last seen and how often it is typically seen
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Period {
+    last_seen: Option<Instant>,
+    typical: Option<Duration>,
+}
+
+impl Period {
+    pub fn observe(&mut self, now: Instant) {
+        if let Some(last_seen) = self.last_seen {
+            let interval = now.saturating_duration_since(last_seen);
+            self.typical = Some(self.typical.map_or(interval, |typical| {
+                typical.mul_f64(1.0 - SMOOTHING) + interval.mul_f64(SMOOTHING)
+            }));
+        }
+        self.last_seen = Some(now);
+    }
+
+    /// The key was deleted so it is not expected to be seen again
+    pub fn forget(&mut self) {
+        *self = Self::default();
+    }
+
+    pub const fn last_seen(&self) -> Option<Instant> {
+        self.last_seen
+    }
+
+    pub const fn typical(&self) -> Option<Duration> {
+        self.typical
+    }
+
+    /// Time without samples after which the key is considered stale.
+    /// A configured timeout replaces the detection based on the typical period.
+    pub fn threshold(&self, settings: &StaleSettings) -> Option<Duration> {
+        settings.timeout.or_else(|| {
+            self.typical
+                .filter(|typical| !typical.is_zero())
+                .map(|typical| typical.mul_f64(settings.factor))
+        })
+    }
+
+    pub fn is_stale(&self, now: Instant, settings: &StaleSettings) -> bool {
+        let Some(last_seen) = self.last_seen else {
+            return false;
+        };
+        self.threshold(settings)
+            .is_some_and(|threshold| now.saturating_duration_since(last_seen) > threshold)
+    }
+}
+
+#[test]
+fn unknown_is_not_stale() {
+    let now = Instant::now();
+    let period = Period::default();
+    assert!(!period.is_stale(now, &StaleSettings::default()));
+}
+
+#[test]
+fn single_sample_has_no_period() {
+    let now = Instant::now();
+    let mut period = Period::default();
+    period.observe(now);
+    assert_eq!(period.typical(), None);
+    let later = now + Duration::from_secs(3600);
+    assert!(!period.is_stale(later, &StaleSettings::default()));
+}
+
+#[test]
+fn stale_after_factor_of_period() {
+    let start = Instant::now();
+    let mut period = Period::default();
+    for second in 0..5 {
+        period.observe(start + Duration::from_secs(second));
+    }
+    let typical = period.typical().unwrap().as_secs_f64();
+    assert!((typical - 1.0).abs() < 0.001);
+
+    let settings = StaleSettings::default();
+    assert!(!period.is_stale(start + Duration::from_secs(6), &settings));
+    assert!(period.is_stale(start + Duration::from_secs(8), &settings));
+}
+
+#[test]
+fn timeout_replaces_period() {
+    let start = Instant::now();
+    let mut period = Period::default();
+    period.observe(start);
+    period.observe(start + Duration::from_secs(1));
+
+    let settings = StaleSettings {
+        factor: 3.0,
+        timeout: Some(Duration::from_secs(10)),
+    };
+    assert!(!period.is_stale(start + Duration::from_secs(5), &settings));
+    assert!(period.is_stale(start + Duration::from_secs(12), &settings));
+}
+
+#[test]
+fn forgotten_is_not_stale() {
+    let start = Instant::now();
+    let mut period = Period::default();
+    period.observe(start);
+    period.forget();
+    let settings = StaleSettings {
+        factor: 3.0,
+        timeout: Some(Duration::from_secs(1)),
+    };
+    assert!(!period.is_stale(start + Duration::from_secs(5), &settings));
+}
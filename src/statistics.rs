@@ -0,0 +1,377 @@
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+
+use crate::zenoh_client::Time;
+
+/// Windows used to calculate the recent message rate
+const RATE_WINDOWS: [(Duration, &str); 3] = [
+    (Duration::from_secs(10), "10s"),
+    (Duration::from_secs(60), "1m"),
+    (Duration::from_secs(5 * 60), "5m"),
+];
+
+pub const DEFAULT_GAP_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Metadata of a single sample relevant for the [`Statistics`]
+pub struct Observation {
+    /// Unknown for samples without a known receive time like deletes
+    pub time: Option<NaiveDateTime>,
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub min: f64,
+    pub avg: f64,
+    pub median: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+impl Summary {
+    fn new(mut values: Vec<f64>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_unstable_by(f64::total_cmp);
+        #[allow(clippy::cast_precision_loss)]
+        let avg = values.iter().sum::<f64>() / values.len() as f64;
+        Some(Self {
+            min: values[0],
+            avg,
+            median: percentile(&values, 50),
+            p99: percentile(&values, 99),
+            max: values[values.len() - 1],
+        })
+    }
+}
+
+/// Nearest-rank percentile of already sorted values
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], percent: u8) -> f64 {
+    let rank = (f64::from(percent) / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    /// Time of the last sample before the gap
+    pub start: NaiveDateTime,
+    pub duration: Duration,
+}
+
+pub struct Statistics {
+    pub messages: usize,
+    pub first_seen: Option<NaiveDateTime>,
+    pub last_seen: Option<NaiveDateTime>,
+    /// Messages per second between the first and the last sample
+    pub overall_rate: Option<f64>,
+    /// Messages per second within the [`RATE_WINDOWS`] before now, at most since the first sample
+    pub recent_rates: Vec<(&'static str, f64)>,
+    /// Time between two consecutive samples in seconds
+    pub inter_arrival: Option<Summary>,
+    /// Payload size in bytes
+    pub payload_size: Option<Summary>,
+    pub gap_threshold: Duration,
+    pub gaps: Vec<Gap>,
+    now: NaiveDateTime,
+}
+
+impl Statistics {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn calculate(
+        observations: &[Observation],
+        now: NaiveDateTime,
+        gap_threshold: Duration,
+    ) -> Self {
+        let mut times = observations
+            .iter()
+            .filter_map(|observation| observation.time)
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+
+        let intervals = times
+            .windows(2)
+            .map(|pair| (pair[0], (pair[1] - pair[0]).to_std().unwrap_or_default()))
+            .collect::<Vec<_>>();
+
+        let overall_rate = match (times.first(), times.last()) {
+            (Some(first), Some(last)) if first < last => {
+                let seconds = (*last - *first).to_std().unwrap_or_default().as_secs_f64();
+                Some(intervals.len() as f64 / seconds)
+            }
+            _ => None,
+        };
+
+        // Windows longer than the observed time would underestimate the rate, so they only cover the observed time
+        let observed = times
+            .first()
+            .and_then(|first| (now - *first).to_std().ok())
+            .filter(|observed| !observed.is_zero());
+        let recent_rates = observed.map_or_else(Vec::new, |observed| {
+            RATE_WINDOWS
+                .iter()
+                .map(|(window, label)| {
+                    let since = chrono::Duration::from_std(*window)
+                        .map_or(NaiveDateTime::MIN, |window| now - window);
+                    let amount = times.iter().filter(|time| **time > since).count();
+                    (
+                        *label,
+                        amount as f64 / (*window).min(observed).as_secs_f64(),
+                    )
+                })
+                .collect()
+        });
+
+        // The silence since the last sample counts as well, it might be the gap that matters
+        let trailing = times
+            .last()
+            .map(|last| (*last, (now - *last).to_std().unwrap_or_default()));
+        let gaps = intervals
+            .iter()
+            .chain(trailing.as_ref())
+            .filter(|(_, duration)| *duration > gap_threshold)
+            .map(|(start, duration)| Gap {
+                start: *start,
+                duration: *duration,
+            })
+            .collect();
+
+        Self {
+            messages: observations.len(),
+            first_seen: times.first().copied(),
+            last_seen: times.last().copied(),
+            overall_rate,
+            recent_rates,
+            inter_arrival: Summary::new(
+                intervals
+                    .iter()
+                    .map(|(_, duration)| duration.as_secs_f64())
+                    .collect(),
+            ),
+            payload_size: Summary::new(
+                observations
+                    .iter()
+                    .map(|observation| observation.size as f64)
+                    .collect(),
+            ),
+            gap_threshold,
+            gaps,
+            now,
+        }
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("Messages:      {}", self.messages)];
+
+        if let Some(first) = self.first_seen {
+            lines.push(format!("First seen:    {}", Time::Local(first)));
+        }
+        if let Some(last) = self.last_seen {
+            let ago = (self.now - last).to_std().unwrap_or_default();
+            lines.push(format!(
+                "Last seen:     {} ({} ago)",
+                Time::Local(last),
+                seconds(ago.as_secs_f64())
+            ));
+        }
+
+        let mut rate = self
+            .overall_rate
+            .map_or_else(|| "-".to_owned(), |rate| format!("{rate:.2}/s overall"));
+        for (label, window_rate) in &self.recent_rates {
+            rate += &format!(", {label}: {window_rate:.2}/s");
+        }
+        lines.push(format!("Rate:          {rate}"));
+
+        if let Some(Summary {
+            min, avg, p99, max, ..
+        }) = self.inter_arrival
+        {
+            lines.push(format!(
+                "Inter-arrival: min {} avg {} p99 {} max {}",
+                seconds(min),
+                seconds(avg),
+                seconds(p99),
+                seconds(max)
+            ));
+        }
+
+        if let Some(Summary {
+            min,
+            avg,
+            median,
+            p99,
+            max,
+        }) = self.payload_size
+        {
+            lines.push(format!(
+                "Payload size:  min {min} avg {avg:.1} p50 {median} p99 {p99} max {max} bytes"
+            ));
+        }
+
+        let threshold = seconds(self.gap_threshold.as_secs_f64());
+        let longest = self.gaps.iter().max_by_key(|gap| gap.duration);
+        lines.push(longest.map_or_else(
+            || format!("Gaps > {threshold}:    none"),
+            |longest| {
+                format!(
+                    "Gaps > {threshold}:    {} (longest {} after {})",
+                    self.gaps.len(),
+                    seconds(longest.duration.as_secs_f64()),
+                    Time::Local(longest.start)
+                )
+            },
+        ));
+
+        lines
+    }
+}
+
+impl std::fmt::Display for Statistics {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.write_str(&self.lines().join("\n"))
+    }
+}
+
+fn seconds(seconds: f64) -> String {
+    if seconds < 10.0 {
+        format!("{seconds:.3}s")
+    } else {
+        format!("{seconds:.1}s")
+    }
+}
+
+#[cfg(test)]
+fn observation(second: u32, size: usize) -> Observation {
+    use chrono::Timelike;
+    Observation {
+        time: Some(Time::datetime_example().with_second(second).unwrap()),
+        size,
+    }
+}
+
+#[test]
+fn percentile_works() {
+    let values = (1..=100).map(f64::from).collect::<Vec<_>>();
+    assert!((percentile(&values, 50) - 50.0).abs() < f64::EPSILON);
+    assert!((percentile(&values, 99) - 99.0).abs() < f64::EPSILON);
+    assert!((percentile(&[42.0], 99) - 42.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn empty_observations() {
+    let now = Time::datetime_example();
+    let statistics = Statistics::calculate(&[], now, DEFAULT_GAP_THRESHOLD);
+    assert_eq!(statistics.messages, 0);
+    assert_eq!(statistics.first_seen, None);
+    assert_eq!(statistics.overall_rate, None);
+    assert_eq!(statistics.inter_arrival, None);
+    assert_eq!(statistics.payload_size, None);
+    assert!(statistics.gaps.is_empty());
+}
+
+#[test]
+fn calculates_intervals_and_sizes() {
+    let observations = [
+        observation(0, 2),
+        observation(1, 4),
+        Observation {
+            time: None,
+            size: 0,
+        },
+        observation(2, 6),
+        observation(10, 8),
+    ];
+    let now = observations[4].time.unwrap();
+    let statistics = Statistics::calculate(&observations, now, DEFAULT_GAP_THRESHOLD);
+    assert_eq!(statistics.messages, 5);
+    assert_eq!(statistics.first_seen, observations[0].time);
+    assert_eq!(statistics.last_seen, observations[4].time);
+
+    let rate = statistics.overall_rate.unwrap();
+    assert!((rate - 0.3).abs() < 0.001);
+
+    let inter_arrival = statistics.inter_arrival.unwrap();
+    assert!((inter_arrival.min - 1.0).abs() < 0.001);
+    assert!((inter_arrival.max - 8.0).abs() < 0.001);
+    assert!((inter_arrival.avg - 10.0 / 3.0).abs() < 0.001);
+
+    let payload_size = statistics.payload_size.unwrap();
+    assert!((payload_size.min - 0.0).abs() < f64::EPSILON);
+    assert!((payload_size.max - 8.0).abs() < f64::EPSILON);
+
+    assert_eq!(
+        statistics.gaps,
+        [Gap {
+            start: observations[3].time.unwrap(),
+            duration: Duration::from_secs(8),
+        }]
+    );
+}
+
+#[test]
+fn recent_rates_only_count_window() {
+    let long_ago = Observation {
+        time: Some(observation(0, 1).time.unwrap() - chrono::Duration::minutes(2)),
+        size: 1,
+    };
+    let observations = [
+        long_ago,
+        observation(0, 1),
+        observation(50, 1),
+        observation(55, 1),
+    ];
+    let now = observations[3].time.unwrap();
+    let statistics = Statistics::calculate(&observations, now, DEFAULT_GAP_THRESHOLD);
+    assert_eq!(statistics.recent_rates[0].0, "10s");
+    assert!((statistics.recent_rates[0].1 - 0.2).abs() < 0.001);
+    assert_eq!(statistics.recent_rates[1].0, "1m");
+    assert!((statistics.recent_rates[1].1 - 0.05).abs() < 0.001);
+}
+
+#[test]
+fn recent_rates_cover_only_the_observed_time() {
+    // One message per second for 30 seconds
+    let observations = (0..30)
+        .map(|second| observation(second, 1))
+        .collect::<Vec<_>>();
+    let now = observations[0].time.unwrap() + chrono::Duration::milliseconds(29_500);
+    let statistics = Statistics::calculate(&observations, now, DEFAULT_GAP_THRESHOLD);
+    for (label, rate) in statistics.recent_rates {
+        assert!((rate - 1.0).abs() < 0.05, "{label}: {rate}");
+    }
+
+    let statistics = Statistics::calculate(
+        &observations[..1],
+        observations[0].time.unwrap(),
+        DEFAULT_GAP_THRESHOLD,
+    );
+    assert!(statistics.recent_rates.is_empty());
+}
+
+#[test]
+fn trailing_silence_is_a_gap() {
+    let observations = [observation(0, 1), observation(1, 1)];
+    let now = observations[1].time.unwrap() + chrono::Duration::seconds(20);
+    let statistics = Statistics::calculate(&observations, now, DEFAULT_GAP_THRESHOLD);
+    assert_eq!(
+        statistics.gaps,
+        [Gap {
+            start: observations[1].time.unwrap(),
+            duration: Duration::from_secs(20),
+        }]
+    );
+
+    let statistics = Statistics::calculate(
+        &observations,
+        observations[1].time.unwrap(),
+        DEFAULT_GAP_THRESHOLD,
+    );
+    assert!(statistics.gaps.is_empty());
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use zenoh::sample::SampleKind;
+use zenoh::Session;
+
+use crate::statistics::{Observation, Statistics};
+use crate::zenoh_client::{self, Time};
+
+pub fn show(
+    session: &Arc<Session>,
+    keyexprs: Vec<String>,
+    duration: Duration,
+    gap_threshold: Duration,
+) -> anyhow::Result<()> {
+    let rx = zenoh_client::subscribe_all(session, keyexprs);
+
+    let mut observations = BTreeMap::<String, Vec<Observation>>::new();
+    let deadline = Instant::now() + duration;
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        let sample = match rx.recv_timeout(remaining) {
+            Ok(sample) => sample,
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(RecvTimeoutError::Disconnected) => {
+                anyhow::bail!("All subscribers stopped before the --duration passed")
+            }
+        };
+        let time = if sample.kind() == SampleKind::Delete {
+            Time::Unknown
+        } else {
+            Time::new_now()
+        };
+        observations
+            .entry(sample.key_expr().as_str().to_owned())
+            .or_default()
+            .push(Observation {
+                time: time.as_optional().copied(),
+                size: sample.payload().len(),
+            });
+    }
+
+    let now = chrono::Local::now().naive_local();
+    let keys = observations.len();
+    let mut total = Vec::new();
+    for (keyexpr, observations) in observations {
+        println!("{keyexpr}");
+        let statistics = Statistics::calculate(&observations, now, gap_threshold);
+        for line in statistics.lines() {
+            println!("  {line}");
+        }
+        println!();
+        total.extend(observations);
+    }
+
+    if keys == 0 {
+        println!(
+            "No samples received within {}",
+            humantime::format_duration(duration)
+        );
+    } else if keys > 1 {
+        println!("Total ({keys} keys)");
+        let statistics = Statistics::calculate(&total, now, gap_threshold);
+        for line in statistics.lines() {
+            println!("  {line}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use zenoh::sample::SampleKind;
+use zenoh::Session;
+
+use crate::staleness::{Period, StaleSettings};
+use crate::zenoh_client::{self, Time};
+
+/// How often all keys are checked for being stale, independent of the amount of samples
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn watch(
+    session: &Arc<Session>,
+    keyexprs: Vec<String>,
+    settings: StaleSettings,
+    exit_on_stale: bool,
+) -> anyhow::Result<()> {
+    let rx = zenoh_client::subscribe_all(session, keyexprs);
+
+    let mut periods = BTreeMap::<String, Period>::new();
+    let mut stale = BTreeSet::<String>::new();
+    let mut next_check = Instant::now() + CHECK_INTERVAL;
+    loop {
+        match rx.recv_timeout(next_check.saturating_duration_since(Instant::now())) {
+            Ok(sample) => {
+                let keyexpr = sample.key_expr().as_str();
+                if sample.kind() == SampleKind::Delete {
+                    // A deleted key is not expected anymore, it did not recover
+                    periods.remove(keyexpr);
+                    if stale.remove(keyexpr) {
+                        println!("{:12} DELETED   {keyexpr}", Time::new_now());
+                    }
+                    continue;
+                }
+                periods
+                    .entry(keyexpr.to_owned())
+                    .or_default()
+                    .observe(Instant::now());
+                if stale.remove(keyexpr) {
+                    println!("{:12} RECOVERED {keyexpr}", Time::new_now());
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                anyhow::bail!("All subscriptions ended");
+            }
+        }
+
+        let now = Instant::now();
+        if now < next_check {
+            continue;
+        }
+        next_check = now + CHECK_INTERVAL;
+        for (keyexpr, period) in &periods {
+            if stale.contains(keyexpr) || !period.is_stale(now, &settings) {
+                continue;
+            }
+            let ago = period
+                .last_seen()
+                .map_or(Duration::ZERO, |last_seen| now.duration_since(last_seen));
+            let typical = period.typical().map_or_else(
+                || "unknown".to_owned(),
+                |typical| format!("{:.1}s", typical.as_secs_f64()),
+            );
+            println!(
+                "{:12} STALE     {keyexpr} last seen {:.1}s ago, typically every {typical}",
+                Time::new_now(),
+                ago.as_secs_f64()
+            );
+            if exit_on_stale {
+                anyhow::bail!("{keyexpr} went stale");
+            }
+            stale.insert(keyexpr.clone());
+        }
+    }
+}
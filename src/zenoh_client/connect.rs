@@ -1,31 +1,107 @@
-use zenoh::config::WhatAmI;
+use std::path::Path;
+use std::time::Duration;
+
 use zenoh::{Config, Session, Wait};
 
+use super::scout;
 use crate::cli::{SessionMode, ZenohConnection};
 
 pub struct SessionInfo {
     pub description: String,
+    /// `TLS` or `mTLS` when the connection is secured
+    pub security: Option<&'static str>,
 }
 
-pub fn connect(connection: ZenohConnection) -> anyhow::Result<(SessionInfo, Session)> {
-    let mut config = Config::default();
-    let mode = match connection.mode {
-        SessionMode::Client => WhatAmI::Client,
-        SessionMode::Peer => WhatAmI::Peer,
-        SessionMode::Router => WhatAmI::Router,
+/// How long `--scout` waits for a router
+const SCOUT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Zenoh config from the config file, the overrides and the mode.
+///
+/// This is enough for scouting, opening a session additionally needs [`apply_session_options`] and the endpoints.
+pub fn base_config(connection: &ZenohConnection) -> anyhow::Result<Config> {
+    let mut config = if let Some(path) = &connection.config {
+        Config::from_file(path).map_err(|err| {
+            anyhow::anyhow!("Failed to load Zenoh config file {}: {err}", path.display())
+        })?
+    } else {
+        Config::default()
     };
-    let mode_value = format!(
-        "\"{}\"",
-        match mode {
-            WhatAmI::Client => "client",
-            WhatAmI::Peer => "peer",
-            WhatAmI::Router => "router",
-        }
-    );
-    config
-        .insert_json5("mode", &mode_value)
-        .map_err(|err| anyhow::anyhow!("Failed to set Zenoh mode: {err}"))?;
 
+    for (key, value) in &connection.cfg {
+        config
+            .insert_json5(key, &cfg_value(value)?)
+            .map_err(|err| anyhow::anyhow!("Invalid --cfg {key}={value}: {err}"))?;
+    }
+
+    // Without a Zenoh config file zenohui defaults to client mode instead of the Zenoh default peer mode
+    let mode = connection.mode.or_else(|| {
+        (connection.config.is_none() && !cfg_sets(connection, "mode"))
+            .then_some(SessionMode::Client)
+    });
+    if let Some(mode) = mode {
+        config
+            .insert_json5("mode", &format!("\"{}\"", mode.as_str()))
+            .map_err(|err| anyhow::anyhow!("Failed to set Zenoh mode: {err}"))?;
+    }
+
+    Ok(config)
+}
+
+pub fn connect(mut connection: ZenohConnection) -> anyhow::Result<(SessionInfo, Session)> {
+    let mut config = base_config(&connection)?;
+
+    // Scouting does not open an authenticated transport so it neither needs TLS files nor a password
+    if connection.scout && connection.peer.is_empty() {
+        let router = scout::first_router(config.clone(), SCOUT_TIMEOUT)?;
+        eprintln!(
+            "Scouted router {} at {}",
+            router.zid,
+            router.locators.join(", ")
+        );
+        connection.peer = router.locators;
+    }
+
+    apply_session_options(&mut config, &connection)?;
+    apply_endpoints(&mut config, &connection)?;
+
+    let info = SessionInfo {
+        description: connection.describe(&effective_mode(&config)),
+        security: connection.security(),
+    };
+
+    let session = zenoh::open(config)
+        .wait()
+        .map_err(|err| {
+            anyhow::anyhow!(
+                "Failed to open Zenoh session. Check --peer/--listen/--mode/--config/--cfg/--scout options: {err}"
+            )
+        })?;
+
+    Ok((info, session))
+}
+
+/// Value of a `--cfg` override as JSON5, plain words like `peer` are taken as strings
+fn cfg_value(value: &str) -> anyhow::Result<String> {
+    if json5::from_str::<serde_json::Value>(value).is_ok() {
+        Ok(value.to_owned())
+    } else {
+        Ok(serde_json::to_string(value)?)
+    }
+}
+
+/// Whether a `--cfg` override sets the key or a parent of it
+fn cfg_sets(connection: &ZenohConnection, key: &str) -> bool {
+    connection.cfg.iter().any(|(cfg_key, _)| {
+        let cfg_key = cfg_key.trim_matches('/');
+        key == cfg_key
+            || key
+                .strip_prefix(cfg_key)
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+/// `--peer` and `--listen` endpoints or the default peer when neither the Zenoh config file nor `--cfg` has endpoints
+fn apply_endpoints(config: &mut Config, connection: &ZenohConnection) -> anyhow::Result<()> {
     if !connection.peer.is_empty() {
         let list = format!(
             "[{}]",
@@ -56,23 +132,203 @@ pub fn connect(connection: ZenohConnection) -> anyhow::Result<(SessionInfo, Sess
             .map_err(|err| anyhow::anyhow!("Invalid --listen endpoint list: {err}"))?;
     }
 
-    if connection.peer.is_empty() && connection.listen.is_empty() {
+    let has_endpoints = !connection.peer.is_empty()
+        || !connection.listen.is_empty()
+        || cfg_sets(connection, "connect/endpoints")
+        || cfg_sets(connection, "listen/endpoints");
+    if connection.config.is_none() && !has_endpoints {
         config
             .insert_json5("connect/endpoints", r#"["tcp/127.0.0.1:7447"]"#)
             .map_err(|err| anyhow::anyhow!("Invalid default peer endpoint: {err}"))?;
     }
+    Ok(())
+}
 
-    let session = zenoh::open(config)
-        .wait()
-        .map_err(|err| {
-            anyhow::anyhow!(
-                "Failed to open Zenoh session. Check --peer/--listen/--mode options: {err}"
-            )
-        })?;
+/// TLS and authentication which are only needed by a session
+fn apply_session_options(config: &mut Config, connection: &ZenohConnection) -> anyhow::Result<()> {
+    apply_tls(config, connection)?;
+    apply_auth(config, connection)
+}
 
-    let info = SessionInfo {
-        description: connection.describe(),
+fn apply_tls(config: &mut Config, connection: &ZenohConnection) -> anyhow::Result<()> {
+    let tls = &connection.tls;
+    let mut insert = |key: &str, value: &str| {
+        config
+            .insert_json5(&format!("transport/link/tls/{key}"), value)
+            .map_err(|err| anyhow::anyhow!("Failed to set TLS option {key}: {err}"))
     };
 
-    Ok((info, session))
+    if let Some(path) = &tls.tls_root_ca {
+        insert(
+            "root_ca_certificate",
+            &pem_file("--tls-root-ca", path, "CERTIFICATE")?,
+        )?;
+    }
+    if let (Some(cert), Some(key)) = (&tls.tls_cert, &tls.tls_key) {
+        let cert = pem_file("--tls-cert", cert, "CERTIFICATE")?;
+        let key = pem_file("--tls-key", key, "PRIVATE KEY")?;
+        insert("connect_certificate", &cert)?;
+        insert("connect_private_key", &key)?;
+        if !connection.listen.is_empty() {
+            insert("listen_certificate", &cert)?;
+            insert("listen_private_key", &key)?;
+        }
+        insert("enable_mtls", "true")?;
+    }
+    if let Some(verify) = tls.tls_server_name_verification {
+        insert("verify_name_on_connect", &verify.to_string())?;
+    }
+    Ok(())
+}
+
+fn apply_auth(config: &mut Config, connection: &ZenohConnection) -> anyhow::Result<()> {
+    // Checked here as the user might come from the profile, which is applied after parsing the arguments
+    let Some(user) = &connection.user else {
+        anyhow::ensure!(
+            connection.password.is_none(),
+            "--password (or ZENOHUI_PASSWORD) needs --user or a user in the profile"
+        );
+        return Ok(());
+    };
+    let password = if let Some(password) = &connection.password {
+        password.0.clone()
+    } else {
+        rpassword::prompt_password(format!("Zenoh password for {user}: "))
+            .map_err(|err| anyhow::anyhow!("Failed to read the password of --user {user}: {err}"))?
+    };
+    config
+        .insert_json5("transport/auth/usrpwd/user", &serde_json::to_string(user)?)
+        .map_err(|err| anyhow::anyhow!("Invalid --user {user}: {err}"))?;
+    // The error might contain the value so it is not shown
+    config
+        .insert_json5(
+            "transport/auth/usrpwd/password",
+            &serde_json::to_string(&password)?,
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to set the password of --user {user}"))?;
+    Ok(())
+}
+
+/// Check the file early to get a clear error instead of a failing TLS handshake.
+///
+/// Returns the path as JSON string for the Zenoh config.
+fn pem_file(option: &str, path: &Path, label: &str) -> anyhow::Result<String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|err| anyhow::anyhow!("Failed to read {option} {}: {err}", path.display()))?;
+    anyhow::ensure!(
+        content.contains("-----BEGIN ") && content.contains(&format!(" {label}-----")),
+        "{option} {} is not a PEM file containing a {label}",
+        path.display()
+    );
+    let path = path
+        .to_str()
+        .ok_or_else(|| anyhow::anyhow!("{option} {} is not valid UTF-8", path.display()))?;
+    Ok(serde_json::to_string(path)?)
+}
+
+/// Mode the session is opened with, Zenoh defaults to peer when the config has none
+fn effective_mode(config: &Config) -> String {
+    config
+        .get_json("mode")
+        .ok()
+        .and_then(|mode| serde_json::from_str::<Option<String>>(&mode).ok().flatten())
+        .unwrap_or_else(|| "peer".to_owned())
+}
+
+#[test]
+fn pem_file_checks_content() {
+    let dir = std::env::temp_dir().join(format!("zenohui-pem-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let cert = dir.join("cert.pem");
+    std::fs::write(
+        &cert,
+        "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----\n",
+    )
+    .unwrap();
+
+    let value = pem_file("--tls-cert", &cert, "CERTIFICATE").unwrap();
+    assert_eq!(
+        value,
+        serde_json::to_string(cert.to_str().unwrap()).unwrap()
+    );
+    assert!(pem_file("--tls-key", &cert, "PRIVATE KEY").is_err());
+    assert!(pem_file("--tls-cert", &dir.join("missing.pem"), "CERTIFICATE").is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(test)]
+fn parse_connection(args: &[&str]) -> ZenohConnection {
+    use clap::Parser;
+    crate::cli::Cli::try_parse_from(std::iter::once("zenohui").chain(args.iter().copied()))
+        .unwrap()
+        .zenoh_connection
+}
+
+#[cfg(test)]
+fn connect_endpoints(config: &Config) -> Vec<String> {
+    serde_json::from_str(&config.get_json("connect/endpoints").unwrap()).unwrap()
+}
+
+#[test]
+fn cfg_value_quotes_plain_words() {
+    assert_eq!(cfg_value("false").unwrap(), "false");
+    assert_eq!(cfg_value("{enabled: false}").unwrap(), "{enabled: false}");
+    assert_eq!(cfg_value("peer").unwrap(), r#""peer""#);
+    assert_eq!(cfg_value(r#"say "hi"\"#).unwrap(), r#""say \"hi\"\\""#);
+}
+
+#[test]
+fn cfg_mode_overrides_default_mode() {
+    let connection = parse_connection(&["--cfg", "mode=peer"]);
+    assert_eq!(effective_mode(&base_config(&connection).unwrap()), "peer");
+
+    let connection = parse_connection(&[]);
+    assert_eq!(effective_mode(&base_config(&connection).unwrap()), "client");
+
+    let connection = parse_connection(&["--cfg", "mode=peer", "--mode", "router"]);
+    assert_eq!(effective_mode(&base_config(&connection).unwrap()), "router");
+}
+
+#[test]
+fn cfg_endpoints_override_default_peer() {
+    let connection = parse_connection(&["--cfg", r#"connect/endpoints=["tcp/10.0.0.1:7447"]"#]);
+    let mut config = base_config(&connection).unwrap();
+    apply_endpoints(&mut config, &connection).unwrap();
+    assert_eq!(connect_endpoints(&config), ["tcp/10.0.0.1:7447"]);
+
+    let connection = parse_connection(&[]);
+    let mut config = base_config(&connection).unwrap();
+    apply_endpoints(&mut config, &connection).unwrap();
+    assert_eq!(connect_endpoints(&config), ["tcp/127.0.0.1:7447"]);
+}
+
+#[test]
+fn cfg_reports_the_real_error() {
+    let connection = parse_connection(&["--cfg", "no/such/key=1"]);
+    let Err(err) = base_config(&connection) else {
+        panic!("the key does not exist");
+    };
+    assert!(err.to_string().contains("no/such/key=1"), "{err}");
+}
+
+#[test]
+fn base_config_ignores_session_options() {
+    let connection = parse_connection(&["--user", "alice", "--tls-root-ca", "/no/such/ca.pem"]);
+    // Neither prompts for the password nor reads the missing PEM file
+    assert!(base_config(&connection).is_ok());
+}
+
+#[test]
+fn password_needs_user() {
+    let connection = parse_connection(&["--password", "secret"]);
+    let mut config = Config::default();
+    assert!(apply_auth(&mut config, &connection).is_err());
+
+    let connection = parse_connection(&["--user", "alice", "--password", "secret"]);
+    apply_auth(&mut config, &connection).unwrap();
+    assert_eq!(
+        config.get_json("transport/auth/usrpwd/user").unwrap(),
+        r#""alice""#
+    );
 }
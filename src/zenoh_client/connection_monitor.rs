@@ -0,0 +1,257 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::zenoh_client::{SessionStatus, Time};
+
+/// Older events are dropped to not grow without bound on a flapping connection
+const MAX_EVENTS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEventKind {
+    /// `whatami` is `router` or `peer`
+    Connected {
+        whatami: &'static str,
+        zid: String,
+    },
+    Disconnected {
+        whatami: &'static str,
+        zid: String,
+    },
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ConnectionEvent {
+    pub time: Time,
+    pub kind: ConnectionEventKind,
+}
+
+impl std::fmt::Display for ConnectionEvent {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ConnectionEventKind::Connected { whatami, zid } => {
+                write!(fmt, "{whatami} {zid} connected")
+            }
+            ConnectionEventKind::Disconnected { whatami, zid } => {
+                write!(fmt, "{whatami} {zid} disconnected")
+            }
+            ConnectionEventKind::Error(error) => write!(fmt, "error: {error}"),
+        }
+    }
+}
+
+/// Derives connection events from the periodically queried [`SessionStatus`]
+/// and keeps them together with the errors of the subscribers
+pub struct ConnectionMonitor {
+    routers: BTreeSet<String>,
+    peers: BTreeSet<String>,
+    connected_since: Option<Instant>,
+    last_disconnect: Option<Time>,
+    error: Option<String>,
+    events: VecDeque<ConnectionEvent>,
+}
+
+impl ConnectionMonitor {
+    pub fn new(status: &SessionStatus, now: Instant) -> Self {
+        let mut monitor = Self {
+            routers: BTreeSet::new(),
+            peers: BTreeSet::new(),
+            connected_since: None,
+            last_disconnect: None,
+            error: None,
+            events: VecDeque::new(),
+        };
+        monitor.update(status, now);
+        monitor
+    }
+
+    pub fn update(&mut self, status: &SessionStatus, now: Instant) {
+        let was_connected = self.is_connected();
+        let routers = status.routers.iter().cloned().collect::<BTreeSet<_>>();
+        let peers = status.peers.iter().cloned().collect::<BTreeSet<_>>();
+        record_changes(&mut self.events, "router", &self.routers, &routers);
+        record_changes(&mut self.events, "peer", &self.peers, &peers);
+        self.routers = routers;
+        self.peers = peers;
+
+        match (was_connected, self.is_connected()) {
+            (false, true) => self.connected_since = Some(now),
+            (true, false) => {
+                self.connected_since = None;
+                self.last_disconnect = Some(Time::new_now());
+            }
+            _ => {}
+        }
+    }
+
+    /// Connected to at least one router or peer
+    pub fn is_connected(&self) -> bool {
+        !self.routers.is_empty() || !self.peers.is_empty()
+    }
+
+    pub fn uptime(&self, now: Instant) -> Option<Duration> {
+        self.connected_since
+            .map(|since| now.saturating_duration_since(since))
+    }
+
+    pub const fn last_disconnect(&self) -> Option<&Time> {
+        self.last_disconnect.as_ref()
+    }
+
+    /// Remember the error until [`Self::clear_error`] is called
+    pub fn record_error(&mut self, error: String) {
+        push_event(&mut self.events, ConnectionEventKind::Error(error.clone()));
+        self.error = Some(error);
+    }
+
+    /// Samples are received again so the last error is resolved.
+    ///
+    /// The error stays in the events.
+    pub fn clear_error(&mut self) {
+        self.error = None;
+    }
+
+    /// Latest error which was not resolved yet
+    pub fn current_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Oldest first, at most [`MAX_EVENTS`]
+    pub const fn events(&self) -> &VecDeque<ConnectionEvent> {
+        &self.events
+    }
+}
+
+fn push_event(events: &mut VecDeque<ConnectionEvent>, kind: ConnectionEventKind) {
+    if events.len() >= MAX_EVENTS {
+        events.pop_front();
+    }
+    events.push_back(ConnectionEvent {
+        time: Time::new_now(),
+        kind,
+    });
+}
+
+fn record_changes(
+    events: &mut VecDeque<ConnectionEvent>,
+    whatami: &'static str,
+    before: &BTreeSet<String>,
+    after: &BTreeSet<String>,
+) {
+    for zid in before.difference(after) {
+        push_event(
+            events,
+            ConnectionEventKind::Disconnected {
+                whatami,
+                zid: zid.clone(),
+            },
+        );
+    }
+    for zid in after.difference(before) {
+        push_event(
+            events,
+            ConnectionEventKind::Connected {
+                whatami,
+                zid: zid.clone(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+fn status(routers: &[&str]) -> SessionStatus {
+    SessionStatus {
+        routers: routers.iter().map(ToString::to_string).collect(),
+        ..SessionStatus::default()
+    }
+}
+
+#[test]
+fn initial_connection_is_an_event() {
+    let now = Instant::now();
+    let monitor = ConnectionMonitor::new(&status(&["a1"]), now);
+    assert!(monitor.is_connected());
+    assert_eq!(monitor.uptime(now), Some(Duration::ZERO));
+    assert_eq!(monitor.events().len(), 1);
+    assert_eq!(monitor.events()[0].to_string(), "router a1 connected");
+}
+
+#[test]
+fn disconnect_and_reconnect() {
+    let start = Instant::now();
+    let mut monitor = ConnectionMonitor::new(&status(&["a1"]), start);
+
+    monitor.update(&status(&[]), start + Duration::from_secs(2));
+    assert!(!monitor.is_connected());
+    assert_eq!(monitor.uptime(start + Duration::from_secs(3)), None);
+    assert!(monitor.last_disconnect().is_some());
+
+    monitor.update(&status(&["b2"]), start + Duration::from_secs(4));
+    assert!(monitor.is_connected());
+    assert_eq!(
+        monitor.uptime(start + Duration::from_secs(10)),
+        Some(Duration::from_secs(6))
+    );
+
+    let events = monitor
+        .events()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [
+            "router a1 connected",
+            "router a1 disconnected",
+            "router b2 connected",
+        ]
+    );
+}
+
+#[test]
+fn unchanged_status_has_no_events() {
+    let now = Instant::now();
+    let mut monitor = ConnectionMonitor::new(&status(&["a1"]), now);
+    monitor.update(&status(&["a1"]), now);
+    assert_eq!(monitor.events().len(), 1);
+}
+
+#[test]
+fn errors_are_kept_in_the_events() {
+    let start = Instant::now();
+    let mut monitor = ConnectionMonitor::new(&status(&["a1"]), start);
+    monitor.record_error("first".to_owned());
+    monitor.update(&status(&[]), start);
+    monitor.record_error("second".to_owned());
+    assert_eq!(monitor.current_error(), Some("second"));
+
+    monitor.clear_error();
+    monitor.update(&status(&["a1"]), start);
+    assert_eq!(monitor.current_error(), None);
+
+    let events = monitor
+        .events()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [
+            "router a1 connected",
+            "error: first",
+            "router a1 disconnected",
+            "error: second",
+            "router a1 connected",
+        ]
+    );
+}
+
+#[test]
+fn events_are_capped() {
+    let mut monitor = ConnectionMonitor::new(&status(&[]), Instant::now());
+    for index in 0..=MAX_EVENTS {
+        monitor.record_error(index.to_string());
+    }
+    assert_eq!(monitor.events().len(), MAX_EVENTS);
+    assert_eq!(monitor.events()[0].to_string(), "error: 1");
+}
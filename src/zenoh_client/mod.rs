@@ -1,7 +1,15 @@
-pub use self::connect::{connect, SessionInfo};
+pub use self::connect::{base_config, connect, SessionInfo};
+pub use self::connection_monitor::{ConnectionEvent, ConnectionEventKind, ConnectionMonitor};
 pub use self::history_entry::HistoryEntry;
+pub use self::scout::discover;
+pub use self::session_status::SessionStatus;
+pub use self::subscribe::subscribe_all;
 pub use self::time::Time;
 
 mod connect;
+mod connection_monitor;
 mod history_entry;
+mod scout;
+mod session_status;
+mod subscribe;
 mod time;
@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use zenoh::config::{WhatAmI, WhatAmIMatcher};
+use zenoh::{Config, Wait};
+
+/// Router or peer found via scouting
+#[derive(Debug, Serialize)]
+pub struct Node {
+    pub zid: String,
+    pub whatami: String,
+    pub locators: Vec<String>,
+}
+
+/// Scout for the given time and return the discovered nodes.
+///
+/// Stops early when `first_only` is set and a node was found.
+pub fn discover(
+    config: Config,
+    what: WhatAmIMatcher,
+    duration: Duration,
+    first_only: bool,
+) -> anyhow::Result<Vec<Node>> {
+    let scout = zenoh::scout(what, config)
+        .wait()
+        .map_err(|err| anyhow::anyhow!("Failed to start scouting: {err}"))?;
+
+    let mut nodes = Vec::<Node>::new();
+    let deadline = Instant::now() + duration;
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        let hello = match scout.recv_timeout(remaining) {
+            Ok(Some(hello)) => hello,
+            Ok(None) => break,
+            Err(err) => return Err(anyhow::anyhow!("Scouting failed: {err}")),
+        };
+        let zid = hello.zid().to_string();
+        let locators = hello.locators().iter().map(ToString::to_string);
+        if let Some(node) = nodes.iter_mut().find(|node| node.zid == zid) {
+            for locator in locators {
+                if !node.locators.contains(&locator) {
+                    node.locators.push(locator);
+                }
+            }
+        } else {
+            nodes.push(Node {
+                zid,
+                whatami: hello.whatami().to_string(),
+                locators: locators.collect(),
+            });
+            if first_only {
+                break;
+            }
+        }
+    }
+    scout.stop();
+    Ok(nodes)
+}
+
+/// Locators of the first router found via scouting
+pub fn first_router(config: Config, duration: Duration) -> anyhow::Result<Node> {
+    discover(config, WhatAmI::Router.into(), duration, true)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No router found via scouting within {}",
+                humantime::format_duration(duration)
+            )
+        })
+}
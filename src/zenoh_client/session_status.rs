@@ -0,0 +1,99 @@
+use serde::Serialize;
+use zenoh::{Session, Wait};
+
+/// Live information about the session and the nodes it is connected to
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionStatus {
+    pub zid: String,
+    pub routers: Vec<String>,
+    pub peers: Vec<String>,
+    /// Locators the session is reachable at
+    pub locators: Vec<String>,
+}
+
+impl SessionStatus {
+    pub fn query(session: &Session) -> Self {
+        let info = session.info();
+        Self {
+            zid: info.zid().wait().to_string(),
+            routers: info
+                .routers_zid()
+                .wait()
+                .map(|zid| zid.to_string())
+                .collect(),
+            peers: info.peers_zid().wait().map(|zid| zid.to_string()).collect(),
+            locators: info
+                .locators()
+                .wait()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    /// Amount of connected routers and peers like `1 router, 2 peers`
+    pub fn summary(&self) -> String {
+        format!(
+            "{}, {}",
+            plural(self.routers.len(), "router"),
+            plural(self.peers.len(), "peer")
+        )
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        fn list(lines: &mut Vec<String>, title: &str, entries: &[String]) {
+            if entries.is_empty() {
+                lines.push(format!("{title:9} -"));
+            }
+            for (index, entry) in entries.iter().enumerate() {
+                let title = if index == 0 { title } else { "" };
+                lines.push(format!("{title:9} {entry}"));
+            }
+        }
+
+        let mut lines = vec![format!("{:9} {}", "ZID", self.zid)];
+        list(&mut lines, "Routers", &self.routers);
+        list(&mut lines, "Peers", &self.peers);
+        list(&mut lines, "Locators", &self.locators);
+        lines
+    }
+}
+
+fn plural(amount: usize, word: &str) -> String {
+    if amount == 1 {
+        format!("{amount} {word}")
+    } else {
+        format!("{amount} {word}s")
+    }
+}
+
+#[test]
+fn summary_works() {
+    let status = SessionStatus {
+        zid: "a1".to_owned(),
+        routers: vec!["b2".to_owned()],
+        peers: Vec::new(),
+        locators: Vec::new(),
+    };
+    assert_eq!(status.summary(), "1 router, 0 peers");
+}
+
+#[test]
+fn lines_works() {
+    let status = SessionStatus {
+        zid: "a1".to_owned(),
+        routers: vec!["b2".to_owned(), "c3".to_owned()],
+        peers: Vec::new(),
+        locators: vec!["tcp/127.0.0.1:7447".to_owned()],
+    };
+    assert_eq!(
+        status.lines(),
+        [
+            "ZID       a1",
+            "Routers   b2",
+            "          c3",
+            "Peers     -",
+            "Locators  tcp/127.0.0.1:7447",
+        ]
+    );
+}
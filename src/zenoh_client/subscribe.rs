@@ -0,0 +1,47 @@
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use zenoh::handlers::fifo::FifoChannel;
+use zenoh::sample::Sample;
+use zenoh::{Session, Wait};
+
+/// Subscribe to each key expression in its own thread and receive the samples of all of them.
+///
+/// The receiver disconnects when all subscriptions ended.
+pub fn subscribe_all(session: &Arc<Session>, keyexprs: Vec<String>) -> mpsc::Receiver<Sample> {
+    let (tx, rx) = mpsc::channel();
+    for keyexpr in keyexprs {
+        let session = Arc::clone(session);
+        let tx = tx.clone();
+        thread::Builder::new()
+            .name(format!("zenoh subscriber {keyexpr}"))
+            .spawn(move || {
+                let subscriber = match session
+                    .declare_subscriber(&keyexpr)
+                    .with(FifoChannel::default())
+                    .wait()
+                {
+                    Ok(subscriber) => subscriber,
+                    Err(err) => {
+                        eprintln!("Failed to subscribe to {keyexpr}: {err}");
+                        return;
+                    }
+                };
+                loop {
+                    match subscriber.recv() {
+                        Ok(sample) => {
+                            if tx.send(sample).is_err() {
+                                break;
+                            }
+                        }
+                        Err(err) => {
+                            eprintln!("Subscriber error: {err}");
+                            break;
+                        }
+                    }
+                }
+            })
+            .expect("should be able to spawn subscriber thread");
+    }
+    rx
+}